@@ -8,6 +8,7 @@ import {
   validateAmount,
   validateUrl,
   validateHash,
+  validateEvidenceHash,
   sanitizeInput,
   truncateAddress,
   formatErrorMessage,
@@ -118,6 +119,21 @@ describe("Validation Utils", () => {
     });
   });
 
+  describe("validateEvidenceHash", () => {
+    it("should accept 32-byte hashes", () => {
+      expect(validateEvidenceHash(new Uint8Array(32).fill(7))).toBe(true);
+      const hash = new Uint8Array(32);
+      hash[31] = 1;
+      expect(validateEvidenceHash(hash)).toBe(true);
+    });
+
+    it("should reject all-zero or wrongly sized hashes", () => {
+      expect(validateEvidenceHash(new Uint8Array(32))).toBe(false);
+      expect(validateEvidenceHash(new Uint8Array(31).fill(7))).toBe(false);
+      expect(validateEvidenceHash(new Uint8Array(33).fill(7))).toBe(false);
+    });
+  });
+
   describe("sanitizeInput", () => {
     it("should remove HTML tags", () => {
       expect(sanitizeInput('<script>alert("xss")</script>')).toBe(
@@ -1,5 +1,8 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short, token,
+    xdr::ToXdr, Address, BytesN, Env, String, Vec,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -8,9 +11,23 @@ pub struct Milestone {
     pub title: String,
     pub description: String,
     pub required_progress: u32,
+    pub course_id: Option<u32>, // progress is read from this course, or the student total when unset
     pub reward_amount: i128,
     pub proof_type: String, // "exam", "attendance", "project", "video"
     pub is_completed: bool,
+    pub deadline: Option<u64>, // unix seconds, pulled in to the scholarship's end date
+    pub on_expiry: ExpiryRule,
+    pub is_expired: bool,
+}
+
+/// What `expire_milestone` does with a milestone's reward once its deadline passes
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExpiryRule {
+    Reclaim,     // reward is refunded to the contributors
+    Extend(u64), // deadline moves out by this many seconds once, then Reclaim applies
+    Forfeit,     // milestone closes unpaid, the reward stays in escrow as surplus
+    RollOver,    // reward moves to the next open milestone, or is refunded if none is left
 }
 
 #[contracttype]
@@ -21,17 +38,286 @@ pub struct Scholarship {
     pub student: Address,
     pub total_amount: i128,
     pub released_amount: i128,
+    pub token: Address,     // SEP-41 token contract held in escrow
     pub token_type: String, // "BRS", "USDC", "XLM"
     pub is_active: bool,
     pub created_at: u64,
+    pub frozen_amount: i128,        // rewards frozen by open disputes
+    pub end_date: Option<u64>,      // milestones not completed by then expire
+    pub quorum: u32,                // verifier approvals needed to release a milestone
+    pub min_contribution: i128,     // smallest deposit accepted
+    pub matching_pool: Option<u32>, // sponsor pool topping up deposits
+}
+
+// Linear vesting schedule of a streaming scholarship
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stream {
+    pub amount: i128, // vests linearly from start to end
+    pub start: u64,
+    pub end: u64,
+    pub cliff: u64, // nothing vests before it; equals start without a cliff
+    pub withdrawn: i128,
+    pub paused_at: Option<u64>,
+    pub paused_for: u64, // seconds spent paused after start, which push the schedule back
+}
+
+// Recurring stipend paid next to a scholarship's milestones, from its own pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stipend {
+    pub amount: i128, // paid per period
+    pub period: u64,  // seconds between payments
+    pub periods: u32,
+    pub start: u64, // the first period is due at start
+    pub paid_periods: u32,
+}
+
+// Funds held for a scholarship, by pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolBalance {
+    pub milestones: i128, // milestone rewards, or the stream of a streaming scholarship
+    pub stipend: i128,
+    pub frozen: i128, // part of `milestones` frozen by open disputes
+    pub total: i128,
+}
+
+// Sponsor funds that match deposits into the scholarships they are attached to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchingPool {
+    pub id: u32,
+    pub sponsor: Address,
+    pub token: Address,
+    pub ratio_bps: u32, // matched per deposited unit, in basis points (10_000 = 1:1)
+    pub cap: i128,      // most matched into any one scholarship
+    pub remaining: i128, // funds not matched or reclaimed yet
+    pub matched: i128,  // total matched so far
+    pub end_date: u64,  // nothing is matched after it, and the sponsor can reclaim the rest
+}
+
+// One contributor's share of a pooled scholarship
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contribution {
+    pub contributor: Address,
+    pub amount: i128,   // total deposited
+    pub refunded: i128, // total refunded pro rata
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Paid,     // arbitrator released the reward to the student
+    Refunded, // arbitrator returned the reward to the donor
+    Split,    // arbitrator divided the reward
+    Expired,  // not resolved before the deadline, funds unfrozen
+}
+
+// Dispute over one milestone, opened by the student or the donor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub scholarship_id: u64,
+    pub milestone_id: u32,
+    pub opened_by: Address,
+    pub reason_hash: BytesN<32>,
+    pub amount: i128, // frozen reward
+    pub opened_at: u64,
+    pub deadline: u64, // after it, anyone can expire an unresolved dispute
+    pub status: DisputeStatus,
+}
+
+// Who completed a milestone
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Completer {
+    Verifier(Address),    // a verifier, or the institution whose exam result was proven
+    Attester(BytesN<32>), // the institution key that signed the attestation
+}
+
+// Evidence kept for a completed milestone
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneCompletion {
+    pub proof_hash: BytesN<32>, // sha256 of the proof
+    pub completed_by: Completer,
+    pub completed_at: u64,
+    pub score: Option<u32>, // from an attestation
+}
+
+// Milestone result signed by a registered institution key, together with the escrow address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub scholarship_id: u64,
+    pub milestone_id: u32,
+    pub student: Address,
+    pub score: u32,
+    pub expiry: u64, // unix seconds, rejected after it
+}
+
+// Mirror of `ExamResult` in the scholarship-milestone contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExamResult {
+    pub student: Address,
+    pub course_id: u32,
+    pub score: u32,
+}
+
+// Mirror of `StudentInfo` in the scholarship-milestone contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StudentInfo {
+    pub address: Address,
+    pub total_progress: u32,
+    pub last_update: u64,
+}
+
+/// The parts of the scholarship-milestone progress contract the escrow uses
+#[contractclient(name = "ProgressClient")]
+pub trait ProgressInterface {
+    fn get_student_info(env: Env, student: Address) -> Option<StudentInfo>;
+    fn get_course_progress(env: Env, student: Address, course_id: u32) -> u32;
+    fn record_payout(
+        env: Env,
+        escrow: Address,
+        student: Address,
+        course_id: Option<u32>,
+        progress: u32,
+    );
+    fn is_escrow(env: Env, escrow: Address) -> bool;
+    fn verify_result(
+        env: Env,
+        batch_id: u32,
+        institution: Address,
+        result: ExamResult,
+        proof: Vec<BytesN<32>>,
+    ) -> bool;
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    ScholarshipNotFound = 1,
+    NotDonor = 2,
+    ScholarshipNotActive = 3,
+    InvalidAmount = 4,
+    NotVerifier = 5,
+    VerifierRequired = 6,
+    VerifierAlreadyExists = 7,
+    VerifierNotFound = 8,
+    MilestoneNotFound = 9,
+    MilestoneAlreadyCompleted = 10,
+    InsufficientFunds = 11,
+    MilestonesIncomplete = 12,
+    AlreadyInitialized = 13,
+    NotInitialized = 14,
+    NotAdmin = 15,
+    ProgressNotReached = 16,
+    NoMilestones = 17,
+    TooManyMilestones = 18,
+    DuplicateMilestoneId = 19,
+    InvalidReward = 20,
+    RewardsExceedTotal = 21,
+    ArbitratorNotSet = 22,
+    Unauthorized = 23, // caller is not the arbitrator, sponsor, student or a participant
+    AlreadyDisputed = 24, // a milestone can be disputed once
+    DisputeNotFound = 25,
+    DisputeNotOpen = 26,
+    MilestoneDisputed = 27,
+    InvalidDeadline = 28,
+    MilestoneExpired = 29,
+    DeadlineNotReached = 30, // deadline, end date or schedule end still ahead
+    InvalidQuorum = 31,
+    AlreadyApproved = 32,
+    ApprovalNotFound = 33,
+    QuorumNotReached = 34,
+    BelowMinContribution = 35,
+    TooManyContributors = 36,
+    MatchingPoolNotFound = 37,
+    TokenMismatch = 38,
+    MatchingPoolAttached = 39,
+    ScheduleNotFound = 40, // no stream or stipend
+    InvalidSchedule = 41,
+    StreamPaused = 42,
+    StreamNotPaused = 43,
+    NothingToWithdraw = 44,
+    InstitutionAlreadyExists = 45,
+    InstitutionNotFound = 46,
+    AttestationExpired = 47,
+    AttestationUsed = 48,
+    InvalidProof = 49,
+    EscrowNotRegistered = 50, // the progress contract does not accept this escrow's payouts
 }
 
+// `Error` is at the 50-variant limit of `contracterror`, so later codes continue here
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AttesterError {
+    AttesterAlreadyExists = 51,
+    AttesterNotFound = 52,
+}
+
+/// Upper bound on milestones per scholarship, keeps the milestone vector small
+pub const MAX_MILESTONES: u32 = 20;
+
+/// Upper bound on distinct contributors to one scholarship, so pro-rata refunds stay bounded
+pub const MAX_CONTRIBUTORS: u32 = 50;
+
+/// Matching ratios are given in basis points of the deposit
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Upper bound on `limit` for paginated reads
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Time the arbitrator has to resolve a dispute, in seconds
+pub const DISPUTE_PERIOD: u64 = 14 * 24 * 60 * 60;
+
+// Storage TTLs, in ledgers (~5s each)
+const DAY_IN_LEDGERS: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - 7 * DAY_IN_LEDGERS;
+
+// Storage layout:
+// - instance: Admin, ProgressContract, Arbitrator, ScholarshipCounter, PoolCounter
+//   (live as long as the contract)
+// - persistent: everything keyed by scholarship, student/donor or matching pool,
+//   extended on every touch
 #[contracttype]
 pub enum DataKey {
+    Admin,
+    ProgressContract, // scholarship-milestone contract read by complete_milestone
+    Arbitrator,       // resolves milestone disputes
     ScholarshipCounter,
+    PoolCounter,
     Scholarship(u64),
     Milestones(u64), // scholarship_id -> Vec<Milestone>
-    Balance(u64),    // scholarship_id -> deposited balance
+    Balance(u64),    // scholarship_id -> token amount held for milestones or a stream
+    Verifiers(u64),  // scholarship_id -> Vec<Address> allowed to complete milestones
+    StudentScholarshipCount(Address),
+    StudentScholarship(Address, u32), // (student, index) -> scholarship_id
+    DonorScholarshipCount(Address),
+    DonorScholarship(Address, u32), // (donor, index) -> scholarship_id
+    Dispute(u64, u32),              // (scholarship_id, milestone_id) -> Dispute
+    Approvals(u64, u32),            // (scholarship_id, milestone_id) -> Vec<Address>
+    Contributions(u64),             // scholarship_id -> Vec<Contribution>
+    MatchingPool(u32),
+    PoolMatched(u32, u64), // (pool_id, scholarship_id) -> amount matched into it
+    Stream(u64),           // scholarship_id -> Stream
+    Stipend(u64),          // scholarship_id -> Stipend
+    StipendBalance(u64),   // scholarship_id -> token amount held for the stipend
+    Institution(BytesN<32>), // ed25519 public key allowed to sign attestations -> true
+    Attesters(u64),        // scholarship_id -> Vec<BytesN<32>> institution keys the donor accepts
+    IndexSlots(u64),       // scholarship_id -> (student index, donor index) of its entries
+    Completion(u64, u32),  // (scholarship_id, milestone_id) -> MilestoneCompletion
 }
 
 #[contract]
@@ -39,198 +325,332 @@ pub struct ScholarshipEscrow;
 
 #[contractimpl]
 impl ScholarshipEscrow {
+    /// Initialize the escrow with an admin and the progress tracking contract
+    /// The progress contract's admin must have registered this escrow with `add_escrow`,
+    /// or payouts could not be recorded there
+    pub fn initialize(env: Env, admin: Address, progress_contract: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        Self::check_registered(&env, &progress_contract)?;
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::ProgressContract, &progress_contract);
+        Self::extend_instance(&env);
+
+        Ok(())
+    }
+
+    /// Point the escrow at a new progress tracking contract (admin only)
+    /// As with `initialize`, it must have registered this escrow
+    pub fn set_progress_contract(
+        env: Env,
+        admin: Address,
+        progress_contract: Address,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::check_registered(&env, &progress_contract)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ProgressContract, &progress_contract);
+        Self::extend_instance(&env);
+
+        Ok(())
+    }
+
+    /// Get the progress tracking contract
+    pub fn get_progress_contract(env: Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ProgressContract)
+            .ok_or(Error::NotInitialized)
+    }
+
     /// Create a new scholarship
+    /// `token` is the asset the escrow holds and pays out, `token_type` its display label
+    /// `verifiers` are the addresses allowed to complete milestones
+    /// Milestones need unique ids and positive rewards, must not be pre-completed,
+    /// and their rewards may not add up to more than `total_amount`
+    /// `end_date` and milestone deadlines must lie in the future, deadlines no later than `end_date`
+    /// Emits `("created", id)` with `(donor, student, total_amount, token)`
+    #[allow(clippy::too_many_arguments)]
     pub fn create_scholarship(
         env: Env,
         donor: Address,
         student: Address,
         total_amount: i128,
+        token: Address,
         token_type: String,
         milestones: Vec<Milestone>,
-    ) -> u64 {
+        verifiers: Vec<Address>,
+        end_date: Option<u64>,
+    ) -> Result<u64, Error> {
         donor.require_auth();
 
-        // Generate scholarship ID
-        let counter: u64 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ScholarshipCounter)
-            .unwrap_or(0);
-        let scholarship_id = counter + 1;
+        if verifiers.is_empty() {
+            return Err(Error::VerifierRequired);
+        }
 
-        // Create scholarship
-        let scholarship = Scholarship {
-            id: scholarship_id,
-            donor: donor.clone(),
-            student: student.clone(),
+        if total_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let now = env.ledger().timestamp();
+        Self::validate_milestones(&milestones, total_amount, now, end_date)?;
+
+        let scholarship = Self::new_scholarship(
+            &env,
+            donor,
+            student,
             total_amount,
-            released_amount: 0,
-            token_type: token_type.clone(),
-            is_active: true,
-            created_at: env.ledger().timestamp(),
-        };
+            token,
+            token_type,
+            end_date,
+        );
+        Ok(Self::store_scholarship(
+            &env,
+            &scholarship,
+            &milestones,
+            &verifiers,
+        ))
+    }
 
-        // Store scholarship
-        env.storage()
-            .persistent()
-            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+    /// Create a streaming scholarship that vests `total_amount` linearly from `start` to `end`
+    /// The student withdraws what has vested with `withdraw_vested`; nothing can be
+    /// withdrawn before `cliff`, when one is set. Streams have no milestones or verifiers.
+    /// Emits `("created", id)` with `(donor, student, total_amount, token)`, then
+    /// `("stream", id)` with `(start, end, cliff)`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stream(
+        env: Env,
+        donor: Address,
+        student: Address,
+        total_amount: i128,
+        token: Address,
+        token_type: String,
+        start: u64,
+        end: u64,
+        cliff: Option<u64>,
+    ) -> Result<u64, Error> {
+        donor.require_auth();
 
-        // Store milestones
-        env.storage()
-            .persistent()
-            .set(&DataKey::Milestones(scholarship_id), &milestones);
+        if total_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-        // Initialize balance to 0
-        env.storage()
-            .persistent()
-            .set(&DataKey::Balance(scholarship_id), &0_i128);
+        let cliff = cliff.unwrap_or(start);
+        if start >= end || end <= env.ledger().timestamp() || cliff < start || cliff > end {
+            return Err(Error::InvalidSchedule);
+        }
 
-        // Update counter
-        env.storage()
-            .persistent()
-            .set(&DataKey::ScholarshipCounter, &scholarship_id);
+        let scholarship =
+            Self::new_scholarship(&env, donor, student, total_amount, token, token_type, None);
+        let scholarship_id =
+            Self::store_scholarship(&env, &scholarship, &Vec::new(&env), &Vec::new(&env));
 
-        scholarship_id
+        let stream = Stream {
+            amount: total_amount,
+            start,
+            end,
+            cliff,
+            withdrawn: 0,
+            paused_at: None,
+            paused_for: 0,
+        };
+        let key = DataKey::Stream(scholarship_id);
+        env.storage().persistent().set(&key, &stream);
+        Self::extend_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("stream"), scholarship_id),
+            (start, end, cliff),
+        );
+
+        Ok(scholarship_id)
     }
 
     /// Deposit funds to scholarship
-    pub fn deposit_funds(env: Env, donor: Address, scholarship_id: u64, amount: i128) {
-        donor.require_auth();
+    /// Anyone can contribute; pulls `amount` of the scholarship token from the contributor
+    /// into the escrow and adds it to their share. Deposits below the scholarship's
+    /// `min_contribution` are rejected. It starts at the total divided among
+    /// `MAX_CONTRIBUTORS`, so dust deposits cannot take up every contributor slot.
+    /// An attached matching pool then tops the deposit up as the sponsor's contribution.
+    /// Deposits fill the stipend pool up to what the stipend still owes, then the milestone pool.
+    /// Emits `("deposit", id)` with `(contributor, amount, new_balance)`, then
+    /// `("matched", id)` with `(pool_id, matched, new_balance)` when a match was made
+    pub fn deposit_funds(
+        env: Env,
+        contributor: Address,
+        scholarship_id: u64,
+        amount: i128,
+    ) -> Result<(), Error> {
+        contributor.require_auth();
 
-        let mut scholarship: Scholarship = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Scholarship(scholarship_id))
-            .expect("Scholarship not found");
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-        // Verify donor
-        if scholarship.donor != donor {
-            panic!("Only scholarship donor can deposit");
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+
+        if amount < scholarship.min_contribution {
+            return Err(Error::BelowMinContribution);
         }
 
+        // Track the contributor's share
+        let mut contributions = Self::get_contributors(env.clone(), scholarship_id)?;
+        Self::add_contribution(&mut contributions, &contributor, amount)?;
+
+        // Move tokens from contributor to escrow
+        token::Client::new(&env, &scholarship.token).transfer(
+            &contributor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
         // Update balance
-        let current_balance: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Balance(scholarship_id))
-            .unwrap_or(0);
-        let new_balance = current_balance + amount;
+        let mut incoming = amount;
+        let mut new_balance = Self::get_balance(env.clone(), scholarship_id).total + amount;
+
+        env.events().publish(
+            (symbol_short!("deposit"), scholarship_id),
+            (contributor.clone(), amount, new_balance),
+        );
+
+        if let Some(pool_id) = scholarship.matching_pool {
+            let matched = Self::match_deposit(
+                &env,
+                pool_id,
+                scholarship_id,
+                &mut contributions,
+                &contributor,
+                amount,
+            )?;
+            if matched > 0 {
+                incoming += matched;
+                new_balance += matched;
+                env.events().publish(
+                    (symbol_short!("matched"), scholarship_id),
+                    (pool_id, matched, new_balance),
+                );
+            }
+        }
+
+        Self::credit_pools(&env, scholarship_id, incoming);
         env.storage()
             .persistent()
-            .set(&DataKey::Balance(scholarship_id), &new_balance);
+            .set(&DataKey::Contributions(scholarship_id), &contributions);
+
+        Ok(())
     }
 
-    /// Complete a milestone and release payment
+    /// Complete a milestone and release payment to the student
+    /// Must be signed by one of the scholarship's verifiers, and the student's
+    /// progress in the progress contract must reach the milestone's `required_progress`,
+    /// counted in the milestone's course when it names one
+    /// With a quorum above 1, the other approvals must already add up to the quorum
+    /// together with this verifier's
+    /// The SHA-256 of `proof_data` is kept with the milestone's completion record
+    /// Emits `("completed", id)` with `(milestone_id, verifier, student, reward_amount)`
     pub fn complete_milestone(
         env: Env,
+        verifier: Address,
         scholarship_id: u64,
         milestone_id: u32,
         proof_data: String,
-    ) -> i128 {
-        // Get scholarship
-        let mut scholarship: Scholarship = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Scholarship(scholarship_id))
-            .expect("Scholarship not found");
-
-        if !scholarship.is_active {
-            panic!("Scholarship is not active");
-        }
+    ) -> Result<i128, Error> {
+        verifier.require_auth();
 
-        // Get milestones
-        let mut milestones: Vec<Milestone> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Milestones(scholarship_id))
-            .expect("Milestones not found");
+        // Proof is checked off-chain by /api/verify-milestone, only its hash is kept
+        let proof_hash = Self::hash_proof(&env, &proof_data);
 
-        // Find and update milestone
-        let mut milestone_found = false;
-        let mut reward_amount = 0_i128;
+        // Get scholarship
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
 
-        for i in 0..milestones.len() {
-            let mut milestone = milestones.get(i).unwrap();
-            if milestone.id == milestone_id {
-                if milestone.is_completed {
-                    panic!("Milestone already completed");
-                }
-                milestone.is_completed = true;
-                reward_amount = milestone.reward_amount;
-                milestones.set(i, milestone);
-                milestone_found = true;
-                break;
-            }
+        let verifiers = Self::get_verifiers(env.clone(), scholarship_id)?;
+        if !verifiers.contains(&verifier) {
+            return Err(Error::NotVerifier);
         }
 
-        if !milestone_found {
-            panic!("Milestone not found");
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
         }
 
-        // Check if enough funds deposited
-        let current_balance: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Balance(scholarship_id))
-            .unwrap_or(0);
-
-        if current_balance < reward_amount {
-            panic!("Insufficient funds in escrow");
+        Self::find_open_milestone(&env, &scholarship, milestone_id)?;
+        let approvals = Self::count_approvals(
+            &env,
+            &verifiers,
+            scholarship_id,
+            milestone_id,
+            Some(&verifier),
+        );
+        if approvals + 1 < scholarship.quorum {
+            return Err(Error::QuorumNotReached);
         }
 
-        // Update scholarship
-        scholarship.released_amount += reward_amount;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
-
-        // Update milestones
-        env.storage()
-            .persistent()
-            .set(&DataKey::Milestones(scholarship_id), &milestones);
-
-        // Update balance
-        env.storage()
-            .persistent()
-            .set(&DataKey::Balance(scholarship_id), &(current_balance - reward_amount));
-
-        reward_amount
+        Self::release_milestone(
+            &env,
+            scholarship,
+            Completer::Verifier(verifier),
+            milestone_id,
+            proof_hash,
+            None,
+            None,
+        )
     }
 
     /// Get scholarship details
-    pub fn get_scholarship(env: Env, scholarship_id: u64) -> Scholarship {
-        env.storage()
+    pub fn get_scholarship(env: Env, scholarship_id: u64) -> Result<Scholarship, Error> {
+        let scholarship = env
+            .storage()
             .persistent()
             .get(&DataKey::Scholarship(scholarship_id))
-            .expect("Scholarship not found")
+            .ok_or(Error::ScholarshipNotFound)?;
+        Self::extend_scholarship(&env, scholarship_id);
+        Ok(scholarship)
     }
 
     /// Get milestones for a scholarship
-    pub fn get_milestones(env: Env, scholarship_id: u64) -> Vec<Milestone> {
+    pub fn get_milestones(env: Env, scholarship_id: u64) -> Result<Vec<Milestone>, Error> {
         env.storage()
             .persistent()
             .get(&DataKey::Milestones(scholarship_id))
-            .expect("Milestones not found")
+            .ok_or(Error::ScholarshipNotFound)
     }
 
-    /// Get scholarship balance
-    pub fn get_balance(env: Env, scholarship_id: u64) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Balance(scholarship_id))
-            .unwrap_or(0)
+    /// Get scholarship balance, broken down by pool
+    pub fn get_balance(env: Env, scholarship_id: u64) -> PoolBalance {
+        let milestones = Self::milestone_balance(&env, scholarship_id);
+        let stipend = Self::stipend_balance(&env, scholarship_id);
+        let frozen = Self::get_scholarship(env.clone(), scholarship_id)
+            .map(|scholarship| scholarship.frozen_amount)
+            .unwrap_or(0);
+        PoolBalance {
+            milestones,
+            stipend,
+            frozen,
+            total: milestones + stipend,
+        }
     }
 
     /// Get completion percentage
-    pub fn get_completion_percentage(env: Env, scholarship_id: u64) -> u32 {
-        let milestones: Vec<Milestone> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Milestones(scholarship_id))
-            .expect("Milestones not found");
+    pub fn get_completion_percentage(env: Env, scholarship_id: u64) -> Result<u32, Error> {
+        let milestones = Self::get_milestones(env.clone(), scholarship_id)?;
 
-        if milestones.len() == 0 {
-            return 0;
+        if milestones.is_empty() {
+            // Streams report the share that has vested
+            return Ok(match Self::get_stream(env.clone(), scholarship_id) {
+                Ok(stream) => (Self::vested_amount(&env, &stream) * 100 / stream.amount) as u32,
+                Err(_) => 0,
+            });
         }
 
         let mut completed = 0_u32;
@@ -241,195 +661,6049 @@ impl ScholarshipEscrow {
             }
         }
 
-        (completed * 100) / (milestones.len() as u32)
+        Ok((completed * 100) / milestones.len())
     }
 
     /// Get all scholarships for a student
+    /// Cancelled scholarships stay listed with `is_active = false`
     pub fn get_student_scholarships(env: Env, student: Address) -> Vec<Scholarship> {
-        let mut result = Vec::new(&env);
-        let counter: u64 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ScholarshipCounter)
-            .unwrap_or(0);
+        let count = Self::get_student_scholarship_count(env.clone(), student.clone());
+        Self::read_index(&env, 0, count, |i| {
+            DataKey::StudentScholarship(student.clone(), i)
+        })
+    }
 
-        for id in 1..=counter {
-            if let Some(scholarship) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Scholarship>(&DataKey::Scholarship(id))
-            {
-                if scholarship.student == student {
-                    result.push_back(scholarship);
-                }
-            }
+    /// Get one page of a student's scholarships, oldest first
+    pub fn get_student_scholarships_page(
+        env: Env,
+        student: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Scholarship> {
+        let count = Self::get_student_scholarship_count(env.clone(), student.clone());
+        let (start, end) = Self::page_bounds(offset, limit, count);
+        Self::read_index(&env, start, end, |i| {
+            DataKey::StudentScholarship(student.clone(), i)
+        })
+    }
+
+    /// Get number of scholarships for a student
+    pub fn get_student_scholarship_count(env: Env, student: Address) -> u32 {
+        let key = DataKey::StudentScholarshipCount(student);
+        let count = env.storage().persistent().get(&key);
+        if count.is_some() {
+            Self::extend_persistent(&env, &key);
         }
-        result
+        count.unwrap_or(0)
     }
 
     /// Get all scholarships created by a donor
+    /// Cancelled scholarships stay listed with `is_active = false`
     pub fn get_donor_scholarships(env: Env, donor: Address) -> Vec<Scholarship> {
-        let mut result = Vec::new(&env);
-        let counter: u64 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ScholarshipCounter)
-            .unwrap_or(0);
+        let count = Self::get_donor_scholarship_count(env.clone(), donor.clone());
+        Self::read_index(&env, 0, count, |i| {
+            DataKey::DonorScholarship(donor.clone(), i)
+        })
+    }
 
-        for id in 1..=counter {
-            if let Some(scholarship) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Scholarship>(&DataKey::Scholarship(id))
-            {
-                if scholarship.donor == donor {
-                    result.push_back(scholarship);
-                }
-            }
+    /// Get one page of a donor's scholarships, oldest first
+    pub fn get_donor_scholarships_page(
+        env: Env,
+        donor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Scholarship> {
+        let count = Self::get_donor_scholarship_count(env.clone(), donor.clone());
+        let (start, end) = Self::page_bounds(offset, limit, count);
+        Self::read_index(&env, start, end, |i| {
+            DataKey::DonorScholarship(donor.clone(), i)
+        })
+    }
+
+    /// Get number of scholarships created by a donor
+    pub fn get_donor_scholarship_count(env: Env, donor: Address) -> u32 {
+        let key = DataKey::DonorScholarshipCount(donor);
+        let count = env.storage().persistent().get(&key);
+        if count.is_some() {
+            Self::extend_persistent(&env, &key);
         }
-        result
+        count.unwrap_or(0)
     }
 
     /// Get all scholarships (admin view)
+    /// Reads every record; prefer `get_scholarships_page` once there are many
     pub fn get_all_scholarships(env: Env) -> Vec<Scholarship> {
         let mut result = Vec::new(&env);
-        let counter: u64 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ScholarshipCounter)
-            .unwrap_or(0);
+        let counter = Self::get_scholarship_count(env.clone());
 
         for id in 1..=counter {
-            if let Some(scholarship) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Scholarship>(&DataKey::Scholarship(id))
-            {
+            if let Ok(scholarship) = Self::get_scholarship(env.clone(), id) {
                 result.push_back(scholarship);
             }
         }
         result
     }
 
-    /// Get total number of scholarships
-    pub fn get_scholarship_count(env: Env) -> u64 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::ScholarshipCounter)
+    /// Get one page of all scholarships (admin view), ordered by id
+    pub fn get_scholarships_page(env: Env, offset: u64, limit: u32) -> Vec<Scholarship> {
+        let mut result = Vec::new(&env);
+        let counter = Self::get_scholarship_count(env.clone());
+        let limit = limit.min(MAX_PAGE_SIZE) as u64;
+
+        let mut id = offset.saturating_add(1);
+        while id <= counter && (result.len() as u64) < limit {
+            if let Ok(scholarship) = Self::get_scholarship(env.clone(), id) {
+                result.push_back(scholarship);
+            }
+            id += 1;
+        }
+        result
+    }
+
+    /// Get total number of scholarships
+    pub fn get_scholarship_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ScholarshipCounter)
             .unwrap_or(0)
     }
 
-    /// Cancel scholarship (donor only, if no milestones completed)
-    pub fn cancel_scholarship(env: Env, donor: Address, scholarship_id: u64) {
-        donor.require_auth();
+    /// Extend the storage TTL of a scholarship, its per-milestone records, its student
+    /// and donor index entries, its matching pool and the contract instance
+    /// Anyone can call this to keep a long-running scholarship from being archived
+    pub fn bump(env: Env, scholarship_id: u64) -> Result<(), Error> {
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        let extend = |key: DataKey| {
+            if env.storage().persistent().has(&key) {
+                Self::extend_persistent(&env, &key);
+            }
+        };
+
+        // Disputes, approvals and completion records are only extended when touched
+        // otherwise, so walk the milestones here
+        for milestone in Self::get_milestones(env.clone(), scholarship_id)?.iter() {
+            extend(DataKey::Dispute(scholarship_id, milestone.id));
+            extend(DataKey::Approvals(scholarship_id, milestone.id));
+            extend(DataKey::Completion(scholarship_id, milestone.id));
+        }
+
+        // Index entries and the matching pool are shared with other scholarships
+        extend(DataKey::StudentScholarshipCount(
+            scholarship.student.clone(),
+        ));
+        extend(DataKey::DonorScholarshipCount(scholarship.donor.clone()));
+        if let Some((student_slot, donor_slot)) = env
+            .storage()
+            .persistent()
+            .get::<_, (u32, u32)>(&DataKey::IndexSlots(scholarship_id))
+        {
+            extend(DataKey::StudentScholarship(
+                scholarship.student.clone(),
+                student_slot,
+            ));
+            extend(DataKey::DonorScholarship(
+                scholarship.donor.clone(),
+                donor_slot,
+            ));
+        }
+        if let Some(pool_id) = scholarship.matching_pool {
+            extend(DataKey::MatchingPool(pool_id));
+            extend(DataKey::PoolMatched(pool_id, scholarship_id));
+        }
+        Ok(())
+    }
+
+    /// Cancel scholarship (donor only)
+    /// A stream first pays the student what has vested so far, a stipend its due periods.
+    /// Payouts for completed milestones stay with the student; the unreleased
+    /// balance is refunded to the contributors pro rata, except rewards frozen by open disputes,
+    /// which stay in escrow until the arbitrator rules. Returns the refunded amount.
+    /// Emits `("cancelled", id)` with `(donor, refunded)`
+    pub fn cancel_scholarship(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+    ) -> Result<i128, Error> {
+        donor.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+
+        Self::settle_stream(&env, &mut scholarship);
+        Self::settle_stipend(&env, &mut scholarship);
+        scholarship.is_active = false;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        let refunded = Self::refund_balance(&env, &scholarship);
+
+        env.events().publish(
+            (symbol_short!("cancelled"), scholarship_id),
+            (donor, refunded),
+        );
+
+        Ok(refunded)
+    }
+
+    /// Close a scholarship once every milestone is completed or expired, any stream
+    /// has fully vested and every stipend period is due (donor only).
+    /// What is still owed from the stream or stipend goes to the student first.
+    /// Returns any surplus left in escrow to the contributors pro rata. Returns the withdrawn amount.
+    /// Emits `("closed", id)` with `(donor, withdrawn)`
+    pub fn close_scholarship(env: Env, donor: Address, scholarship_id: u64) -> Result<i128, Error> {
+        donor.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+
+        let milestones = Self::get_milestones(env.clone(), scholarship_id)?;
+        for milestone in milestones.iter() {
+            if !milestone.is_completed && !milestone.is_expired {
+                return Err(Error::MilestonesIncomplete);
+            }
+        }
+
+        if let Ok(stream) = Self::get_stream(env.clone(), scholarship_id) {
+            if Self::vested_amount(&env, &stream) < stream.amount {
+                return Err(Error::DeadlineNotReached);
+            }
+        }
+
+        if let Ok(stipend) = Self::get_stipend(env.clone(), scholarship_id) {
+            if Self::due_periods(&env, &stipend) < stipend.periods {
+                return Err(Error::DeadlineNotReached);
+            }
+        }
+
+        Self::settle_stream(&env, &mut scholarship);
+        Self::settle_stipend(&env, &mut scholarship);
+        scholarship.is_active = false;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        let withdrawn = Self::refund_balance(&env, &scholarship);
+
+        env.events().publish(
+            (symbol_short!("closed"), scholarship_id),
+            (donor, withdrawn),
+        );
+
+        Ok(withdrawn)
+    }
+
+    /// Add a verifier to a scholarship (donor only)
+    /// Emits `("ver_add", id)` with `verifier`
+    pub fn add_verifier(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+        verifier: Address,
+    ) -> Result<(), Error> {
+        donor.require_auth();
+
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+
+        let mut verifiers = Self::get_verifiers(env.clone(), scholarship_id)?;
+        if verifiers.contains(&verifier) {
+            return Err(Error::VerifierAlreadyExists);
+        }
+
+        verifiers.push_back(verifier.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Verifiers(scholarship_id), &verifiers);
+
+        env.events()
+            .publish((symbol_short!("ver_add"), scholarship_id), verifier);
+
+        Ok(())
+    }
+
+    /// Remove a verifier from a scholarship (donor only)
+    /// At least one verifier, and no fewer than the quorum, must remain
+    /// Emits `("ver_rm", id)` with `verifier`
+    pub fn remove_verifier(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+        verifier: Address,
+    ) -> Result<(), Error> {
+        donor.require_auth();
+
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+
+        let mut verifiers = Self::get_verifiers(env.clone(), scholarship_id)?;
+        let index = verifiers
+            .first_index_of(&verifier)
+            .ok_or(Error::VerifierNotFound)?;
+
+        if verifiers.len() == 1 {
+            return Err(Error::VerifierRequired);
+        }
+
+        if verifiers.len() - 1 < scholarship.quorum {
+            return Err(Error::InvalidQuorum);
+        }
+
+        verifiers.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Verifiers(scholarship_id), &verifiers);
+
+        env.events()
+            .publish((symbol_short!("ver_rm"), scholarship_id), verifier);
+
+        Ok(())
+    }
+
+    /// Get verifiers for a scholarship
+    pub fn get_verifiers(env: Env, scholarship_id: u64) -> Result<Vec<Address>, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Verifiers(scholarship_id))
+            .ok_or(Error::ScholarshipNotFound)
+    }
+
+    /// Get every contributor's share of a scholarship, in order of first deposit
+    pub fn get_contributors(env: Env, scholarship_id: u64) -> Result<Vec<Contribution>, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contributions(scholarship_id))
+            .ok_or(Error::ScholarshipNotFound)
+    }
+
+    /// Set the smallest deposit a scholarship accepts (donor only), at least 1
+    /// Emits `("min_contr", id)` with `min_contribution`
+    pub fn set_min_contribution(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+        min_contribution: i128,
+    ) -> Result<(), Error> {
+        donor.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+
+        if min_contribution <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        scholarship.min_contribution = min_contribution;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        env.events().publish(
+            (symbol_short!("min_contr"), scholarship_id),
+            min_contribution,
+        );
+
+        Ok(())
+    }
+
+    /// Require `quorum` verifier approvals before a milestone is released (donor only)
+    /// `quorum` must be between 1 and the number of verifiers
+    /// Emits `("quorum", id)` with `quorum`
+    pub fn set_quorum(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+        quorum: u32,
+    ) -> Result<(), Error> {
+        donor.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+
+        let verifiers = Self::get_verifiers(env.clone(), scholarship_id)?;
+        if quorum == 0 || quorum > verifiers.len() {
+            return Err(Error::InvalidQuorum);
+        }
+
+        scholarship.quorum = quorum;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        env.events()
+            .publish((symbol_short!("quorum"), scholarship_id), quorum);
+
+        Ok(())
+    }
+
+    /// Approve a milestone as one of the scholarship's verifiers
+    /// The approval that brings the milestone to the quorum releases it, as
+    /// `complete_milestone` would, keeping the hash of its `proof_data`.
+    /// If the student's progress or the escrow's funds fall short, the approval is
+    /// still recorded and one of the approving verifiers releases the milestone later
+    /// with `complete_milestone`.
+    /// Returns the number of approvals.
+    /// Emits `("approved", id)` with `(milestone_id, verifier, approvals)`
+    pub fn approve_milestone(
+        env: Env,
+        verifier: Address,
+        scholarship_id: u64,
+        milestone_id: u32,
+        proof_data: String,
+    ) -> Result<u32, Error> {
+        verifier.require_auth();
+        let proof_hash = Self::hash_proof(&env, &proof_data);
+
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        let verifiers = Self::get_verifiers(env.clone(), scholarship_id)?;
+        if !verifiers.contains(&verifier) {
+            return Err(Error::NotVerifier);
+        }
+
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+
+        Self::find_open_milestone(&env, &scholarship, milestone_id)?;
+
+        let key = DataKey::Approvals(scholarship_id, milestone_id);
+        let mut approvals = Self::get_approvals(env.clone(), scholarship_id, milestone_id);
+        if approvals.contains(&verifier) {
+            return Err(Error::AlreadyApproved);
+        }
+        approvals.push_back(verifier.clone());
+        env.storage().persistent().set(&key, &approvals);
+        Self::extend_persistent(&env, &key);
+
+        let count = Self::count_approvals(
+            &env,
+            &verifiers,
+            scholarship_id,
+            milestone_id,
+            Some(&verifier),
+        ) + 1;
+
+        env.events().publish(
+            (symbol_short!("approved"), scholarship_id),
+            (milestone_id, verifier.clone(), count),
+        );
+
+        if count >= scholarship.quorum {
+            match Self::release_milestone(
+                &env,
+                scholarship,
+                Completer::Verifier(verifier),
+                milestone_id,
+                proof_hash,
+                None,
+                None,
+            ) {
+                Ok(_) | Err(Error::ProgressNotReached) | Err(Error::InsufficientFunds) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Withdraw an approval before the milestone is released
+    /// Returns the number of approvals left.
+    /// Emits `("unapprove", id)` with `(milestone_id, verifier)`
+    pub fn withdraw_approval(
+        env: Env,
+        verifier: Address,
+        scholarship_id: u64,
+        milestone_id: u32,
+    ) -> Result<u32, Error> {
+        verifier.require_auth();
+
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+
+        let milestone = Self::get_milestones(env.clone(), scholarship_id)?
+            .iter()
+            .find(|milestone| milestone.id == milestone_id)
+            .ok_or(Error::MilestoneNotFound)?;
+        if milestone.is_completed {
+            return Err(Error::MilestoneAlreadyCompleted);
+        }
+
+        let key = DataKey::Approvals(scholarship_id, milestone_id);
+        let mut approvals = Self::get_approvals(env.clone(), scholarship_id, milestone_id);
+        let index = approvals
+            .first_index_of(&verifier)
+            .ok_or(Error::ApprovalNotFound)?;
+        approvals.remove(index);
+        env.storage().persistent().set(&key, &approvals);
+        Self::extend_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("unapprove"), scholarship.id),
+            (milestone_id, verifier),
+        );
+
+        Ok(approvals.len())
+    }
+
+    /// Get the verifiers who approved a milestone, in approval order
+    pub fn get_approvals(env: Env, scholarship_id: u64, milestone_id: u32) -> Vec<Address> {
+        let key = DataKey::Approvals(scholarship_id, milestone_id);
+        let approvals = env.storage().persistent().get(&key);
+        if approvals.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        approvals.unwrap_or(Vec::new(&env))
+    }
+
+    /// Apply a milestone's `on_expiry` rule once its deadline or the scholarship's
+    /// end date has passed (anyone)
+    /// `Extend` cannot move a deadline past the end date and falls back to `Reclaim`
+    /// once the scholarship has ended. Returns the rule that was applied.
+    /// Emits `("expired", id)` with `(milestone_id, rule, refunded)`
+    pub fn expire_milestone(
+        env: Env,
+        scholarship_id: u64,
+        milestone_id: u32,
+    ) -> Result<ExpiryRule, Error> {
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+
+        let mut milestones = Self::get_milestones(env.clone(), scholarship_id)?;
+        let index = milestones
+            .iter()
+            .position(|milestone| milestone.id == milestone_id)
+            .ok_or(Error::MilestoneNotFound)? as u32;
+        let mut milestone = milestones.get(index).unwrap();
+
+        if milestone.is_completed {
+            return Err(Error::MilestoneAlreadyCompleted);
+        }
+        if milestone.is_expired {
+            return Err(Error::MilestoneExpired);
+        }
+        if !Self::is_expired(&env, &scholarship, &milestone) {
+            return Err(Error::DeadlineNotReached);
+        }
+        if Self::has_open_dispute(&env, scholarship_id, milestone_id) {
+            return Err(Error::MilestoneDisputed);
+        }
+
+        let now = env.ledger().timestamp();
+        let ended = scholarship.end_date.is_some_and(|end| now > end);
+        let next = (index + 1..milestones.len()).find(|&i| {
+            let other = milestones.get(i).unwrap();
+            !other.is_completed && !other.is_expired
+        });
+        let rule = match (milestone.on_expiry, next) {
+            (ExpiryRule::Extend(_), _) if ended => ExpiryRule::Reclaim,
+            (ExpiryRule::RollOver, None) => ExpiryRule::Reclaim,
+            (rule, _) => rule,
+        };
+
+        let mut refunded = 0_i128;
+        match rule {
+            ExpiryRule::Extend(seconds) => {
+                let deadline = Self::effective_deadline(&scholarship, &milestone)
+                    .unwrap()
+                    .saturating_add(seconds);
+                milestone.deadline = Some(
+                    scholarship
+                        .end_date
+                        .map_or(deadline, |end| deadline.min(end)),
+                );
+                milestone.on_expiry = ExpiryRule::Reclaim;
+            }
+            ExpiryRule::RollOver => {
+                let next = next.unwrap();
+                let mut other = milestones.get(next).unwrap();
+                other.reward_amount += milestone.reward_amount;
+                milestones.set(next, other);
+                milestone.is_expired = true;
+            }
+            ExpiryRule::Forfeit => milestone.is_expired = true,
+            ExpiryRule::Reclaim => {
+                milestone.is_expired = true;
+
+                let balance = Self::milestone_balance(&env, scholarship_id);
+                refunded = milestone
+                    .reward_amount
+                    .min(balance - scholarship.frozen_amount)
+                    .max(0);
+                if refunded > 0 {
+                    env.storage()
+                        .persistent()
+                        .set(&DataKey::Balance(scholarship_id), &(balance - refunded));
+                    Self::refund_pro_rata(&env, &scholarship, refunded);
+                }
+            }
+        }
+
+        milestones.set(index, milestone);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Milestones(scholarship_id), &milestones);
+
+        env.events().publish(
+            (symbol_short!("expired"), scholarship_id),
+            (milestone_id, rule, refunded),
+        );
+
+        Ok(rule)
+    }
+
+    /// Withdraw what has vested in a streaming scholarship (student only)
+    /// Pays out no more than the escrow holds. Returns the withdrawn amount.
+    /// Emits `("vested", id)` with `(student, amount, withdrawn_total)`
+    pub fn withdraw_vested(env: Env, student: Address, scholarship_id: u64) -> Result<i128, Error> {
+        student.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if scholarship.student != student {
+            return Err(Error::Unauthorized);
+        }
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+        Self::get_stream(env.clone(), scholarship_id)?;
+
+        let amount = Self::settle_stream(&env, &mut scholarship);
+        if amount == 0 {
+            return Err(Error::NothingToWithdraw);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        Ok(amount)
+    }
+
+    /// Pause a stream, e.g. while the student is on academic probation (donor only)
+    /// Nothing vests while paused; the rest of the schedule moves back by the part of
+    /// the pause after the stream's start.
+    /// Emits `("paused", id)` with `timestamp`
+    pub fn pause_stream(env: Env, donor: Address, scholarship_id: u64) -> Result<(), Error> {
+        let mut stream = Self::donor_stream(&env, &donor, scholarship_id)?;
+        if stream.paused_at.is_some() {
+            return Err(Error::StreamPaused);
+        }
+
+        let now = env.ledger().timestamp();
+        stream.paused_at = Some(now);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(scholarship_id), &stream);
+
+        env.events()
+            .publish((symbol_short!("paused"), scholarship_id), now);
+
+        Ok(())
+    }
+
+    /// Resume a paused stream (donor only)
+    /// Emits `("resumed", id)` with `paused_for`, the total seconds spent paused
+    pub fn resume_stream(env: Env, donor: Address, scholarship_id: u64) -> Result<(), Error> {
+        let mut stream = Self::donor_stream(&env, &donor, scholarship_id)?;
+        let paused_at = stream.paused_at.ok_or(Error::StreamNotPaused)?;
+
+        // Only time the schedule would have spent vesting counts; nothing vests before start
+        let frozen_at = paused_at - stream.paused_for;
+        let resumed_at = env.ledger().timestamp() - stream.paused_for;
+        stream.paused_for += resumed_at.saturating_sub(frozen_at.max(stream.start));
+        stream.paused_at = None;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Stream(scholarship_id), &stream);
+
+        env.events().publish(
+            (symbol_short!("resumed"), scholarship_id),
+            stream.paused_for,
+        );
+
+        Ok(())
+    }
+
+    /// Get the vesting schedule of a streaming scholarship
+    pub fn get_stream(env: Env, scholarship_id: u64) -> Result<Stream, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Stream(scholarship_id))
+            .ok_or(Error::ScheduleNotFound)
+    }
+
+    /// Get how much of a stream has vested so far, withdrawn or not
+    pub fn get_vested(env: Env, scholarship_id: u64) -> Result<i128, Error> {
+        let stream = Self::get_stream(env.clone(), scholarship_id)?;
+        Ok(Self::vested_amount(&env, &stream))
+    }
+
+    /// Add a recurring stipend to a scholarship (donor only, one stipend per scholarship)
+    /// `amount` is paid every `period` seconds, `periods` times, the first at `start`.
+    /// The stipend and the milestone rewards must fit in the scholarship's `total_amount`,
+    /// and the last payment must fall before its end date.
+    /// Funds already deposited beyond what the milestones and the stream still need move
+    /// to the stipend pool.
+    /// Emits `("stip_set", id)` with `(amount, period, periods, start)`
+    pub fn add_stipend(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+        amount: i128,
+        period: u64,
+        periods: u32,
+        start: u64,
+    ) -> Result<(), Error> {
+        donor.require_auth();
+
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Stipend(scholarship_id))
+        {
+            return Err(Error::InvalidSchedule);
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let last_payment = (periods as u64)
+            .checked_sub(1)
+            .and_then(|n| n.checked_mul(period))
+            .and_then(|offset| offset.checked_add(start))
+            .ok_or(Error::InvalidSchedule)?;
+        if period == 0 || scholarship.end_date.is_some_and(|end| last_payment > end) {
+            return Err(Error::InvalidSchedule);
+        }
+
+        // The stipend is paid on top of milestone rewards or a stream
+        let committed: i128 = Self::get_milestones(env.clone(), scholarship_id)?
+            .iter()
+            .map(|milestone| milestone.reward_amount)
+            .sum::<i128>()
+            + Self::get_stream(env.clone(), scholarship_id).map_or(0, |stream| stream.amount);
+        let stipend_total = amount
+            .checked_mul(periods as i128)
+            .ok_or(Error::RewardsExceedTotal)?;
+        if committed + stipend_total > scholarship.total_amount {
+            return Err(Error::RewardsExceedTotal);
+        }
+
+        let stipend = Stipend {
+            amount,
+            period,
+            periods,
+            start,
+            paid_periods: 0,
+        };
+        let key = DataKey::Stipend(scholarship_id);
+        env.storage().persistent().set(&key, &stipend);
+        Self::extend_persistent(&env, &key);
+
+        // Funds deposited before the stipend all went to the milestone pool; the part
+        // the unpaid milestones and the stream don't need now funds the stipend
+        let outstanding: i128 = Self::get_milestones(env.clone(), scholarship_id)?
+            .iter()
+            .filter(|milestone| !milestone.is_completed && !milestone.is_expired)
+            .map(|milestone| milestone.reward_amount)
+            .sum::<i128>()
+            + Self::get_stream(env.clone(), scholarship_id)
+                .map_or(0, |stream| stream.amount - stream.withdrawn);
+        let balance = Self::milestone_balance(&env, scholarship_id);
+        let surplus = (balance - outstanding).min(stipend_total);
+        if surplus > 0 {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Balance(scholarship_id), &(balance - surplus));
+            let pool_key = DataKey::StipendBalance(scholarship_id);
+            env.storage().persistent().set(&pool_key, &surplus);
+            Self::extend_persistent(&env, &pool_key);
+        }
+
+        env.events().publish(
+            (symbol_short!("stip_set"), scholarship_id),
+            (amount, period, periods, start),
+        );
+
+        Ok(())
+    }
+
+    /// Claim every stipend period that is due and funded (student only)
+    /// Returns the amount paid.
+    /// Emits `("stipend", id)` with `(student, amount, paid_periods)`
+    pub fn claim_stipend(env: Env, student: Address, scholarship_id: u64) -> Result<i128, Error> {
+        student.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if scholarship.student != student {
+            return Err(Error::Unauthorized);
+        }
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+        Self::get_stipend(env.clone(), scholarship_id)?;
+
+        let amount = Self::settle_stipend(&env, &mut scholarship);
+        if amount == 0 {
+            return Err(Error::NothingToWithdraw);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        Ok(amount)
+    }
+
+    /// Get the stipend schedule of a scholarship
+    pub fn get_stipend(env: Env, scholarship_id: u64) -> Result<Stipend, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Stipend(scholarship_id))
+            .ok_or(Error::ScheduleNotFound)
+    }
+
+    /// Create a matching pool funded by `sponsor` with `amount` of `token` (sponsor only)
+    /// Each deposit into an attached scholarship is matched at `ratio_bps` basis points,
+    /// up to `cap` per scholarship, until the pool runs dry or `end_date` passes
+    /// Emits `("pool_new", pool_id)` with `(sponsor, token, amount, ratio_bps, cap, end_date)`
+    pub fn create_matching_pool(
+        env: Env,
+        sponsor: Address,
+        token: Address,
+        amount: i128,
+        ratio_bps: u32,
+        cap: i128,
+        end_date: u64,
+    ) -> Result<u32, Error> {
+        sponsor.require_auth();
+        Self::extend_instance(&env);
+
+        if amount <= 0 || cap <= 0 || ratio_bps == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if end_date <= env.ledger().timestamp() {
+            return Err(Error::InvalidDeadline);
+        }
+
+        token::Client::new(&env, &token).transfer(
+            &sponsor,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let pool_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PoolCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::PoolCounter, &pool_id);
+
+        let pool = MatchingPool {
+            id: pool_id,
+            sponsor: sponsor.clone(),
+            token: token.clone(),
+            ratio_bps,
+            cap,
+            remaining: amount,
+            matched: 0,
+            end_date,
+        };
+        let key = DataKey::MatchingPool(pool_id);
+        env.storage().persistent().set(&key, &pool);
+        Self::extend_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("pool_new"), pool_id),
+            (sponsor, token, amount, ratio_bps, cap, end_date),
+        );
+
+        Ok(pool_id)
+    }
+
+    /// Attach a matching pool to a scholarship that pays in the same token
+    /// (sponsor, co-signed by the scholarship's donor)
+    /// A scholarship takes one matching pool.
+    /// Emits `("pool_att", pool_id)` with `scholarship_id`
+    pub fn attach_matching_pool(
+        env: Env,
+        sponsor: Address,
+        pool_id: u32,
+        scholarship_id: u64,
+    ) -> Result<(), Error> {
+        sponsor.require_auth();
+
+        let pool = Self::get_matching_pool(env.clone(), pool_id)?;
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+        scholarship.donor.require_auth();
+
+        if pool.sponsor != sponsor {
+            return Err(Error::Unauthorized);
+        }
+        if pool.token != scholarship.token {
+            return Err(Error::TokenMismatch);
+        }
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+        if scholarship.matching_pool.is_some() {
+            return Err(Error::MatchingPoolAttached);
+        }
+
+        scholarship.matching_pool = Some(pool_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        env.events()
+            .publish((symbol_short!("pool_att"), pool_id), scholarship_id);
+
+        Ok(())
+    }
+
+    /// Detach a scholarship's matching pool so another one can be attached (donor only)
+    /// What the pool already matched stays the sponsor's share.
+    /// Emits `("pool_det", pool_id)` with `scholarship_id`
+    pub fn detach_matching_pool(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+    ) -> Result<(), Error> {
+        donor.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+        let pool_id = scholarship
+            .matching_pool
+            .ok_or(Error::MatchingPoolNotFound)?;
+
+        scholarship.matching_pool = None;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        env.events()
+            .publish((symbol_short!("pool_det"), pool_id), scholarship_id);
+
+        Ok(())
+    }
+
+    /// Return what is left in a matching pool to its sponsor once the pool has ended
+    /// Returns the reclaimed amount.
+    /// Emits `("pool_recl", pool_id)` with `(sponsor, amount)`
+    pub fn reclaim_matching_pool(env: Env, sponsor: Address, pool_id: u32) -> Result<i128, Error> {
+        sponsor.require_auth();
+
+        let mut pool = Self::get_matching_pool(env.clone(), pool_id)?;
+        Self::extend_instance(&env);
+
+        if pool.sponsor != sponsor {
+            return Err(Error::Unauthorized);
+        }
+        if env.ledger().timestamp() <= pool.end_date {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        let amount = pool.remaining;
+        pool.remaining = 0;
+        env.storage()
+            .persistent()
+            .set(&DataKey::MatchingPool(pool_id), &pool);
+
+        if amount > 0 {
+            token::Client::new(&env, &pool.token).transfer(
+                &env.current_contract_address(),
+                &sponsor,
+                &amount,
+            );
+        }
+
+        env.events()
+            .publish((symbol_short!("pool_recl"), pool_id), (sponsor, amount));
+
+        Ok(amount)
+    }
+
+    /// Get a matching pool, including what is left in it
+    pub fn get_matching_pool(env: Env, pool_id: u32) -> Result<MatchingPool, Error> {
+        let key = DataKey::MatchingPool(pool_id);
+        let pool = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::MatchingPoolNotFound)?;
+        Self::extend_persistent(&env, &key);
+        Ok(pool)
+    }
+
+    /// Get how much a matching pool has matched into one scholarship
+    pub fn get_pool_matched(env: Env, pool_id: u32, scholarship_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PoolMatched(pool_id, scholarship_id))
+            .unwrap_or(0)
+    }
+
+    /// Set the arbitrator who resolves milestone disputes (admin only)
+    /// Emits `("arb_set",)` with `arbitrator`
+    pub fn set_arbitrator(env: Env, admin: Address, arbitrator: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Arbitrator, &arbitrator);
+        Self::extend_instance(&env);
+
+        env.events()
+            .publish((symbol_short!("arb_set"),), arbitrator);
+
+        Ok(())
+    }
+
+    /// Get the dispute arbitrator
+    pub fn get_arbitrator(env: Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Arbitrator)
+            .ok_or(Error::ArbitratorNotSet)
+    }
+
+    /// Dispute a milestone (student or donor)
+    /// Freezes the milestone's reward so it can be neither paid out nor refunded
+    /// until the arbitrator rules, or `DISPUTE_PERIOD` passes without a ruling
+    /// Emits `("disputed", id)` with `(milestone_id, caller, reason_hash, deadline)`
+    pub fn open_dispute(
+        env: Env,
+        caller: Address,
+        scholarship_id: u64,
+        milestone_id: u32,
+        reason_hash: BytesN<32>,
+    ) -> Result<Dispute, Error> {
+        caller.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if caller != scholarship.student && caller != scholarship.donor {
+            return Err(Error::Unauthorized);
+        }
+
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+
+        Self::get_arbitrator(env.clone())?;
+
+        let milestones = Self::get_milestones(env.clone(), scholarship_id)?;
+        let milestone = milestones
+            .iter()
+            .find(|milestone| milestone.id == milestone_id)
+            .ok_or(Error::MilestoneNotFound)?;
+        if milestone.is_completed {
+            return Err(Error::MilestoneAlreadyCompleted);
+        }
+
+        if Self::is_expired(&env, &scholarship, &milestone) {
+            return Err(Error::MilestoneExpired);
+        }
+
+        // One dispute per milestone: reopening after an expiry could freeze the reward
+        // forever, and the record of the first one is kept
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Dispute(scholarship_id, milestone_id))
+        {
+            return Err(Error::AlreadyDisputed);
+        }
+
+        let balance = Self::milestone_balance(&env, scholarship_id);
+        if balance - scholarship.frozen_amount < milestone.reward_amount {
+            return Err(Error::InsufficientFunds);
+        }
+
+        scholarship.frozen_amount += milestone.reward_amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        let now = env.ledger().timestamp();
+        let dispute = Dispute {
+            scholarship_id,
+            milestone_id,
+            opened_by: caller.clone(),
+            reason_hash: reason_hash.clone(),
+            amount: milestone.reward_amount,
+            opened_at: now,
+            deadline: now + DISPUTE_PERIOD,
+            status: DisputeStatus::Open,
+        };
+        let key = DataKey::Dispute(scholarship_id, milestone_id);
+        env.storage().persistent().set(&key, &dispute);
+        Self::extend_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("disputed"), scholarship_id),
+            (milestone_id, caller, reason_hash, dispute.deadline),
+        );
+
+        Ok(dispute)
+    }
+
+    /// Rule on an open dispute (arbitrator only)
+    /// `student_amount` of the frozen reward goes to the student and the rest back to
+    /// the contributors: all of it pays the milestone, none refunds it, anything between splits it.
+    /// The milestone is settled either way and cannot be completed again: it counts as
+    /// completed when the student receives any of the reward, and is closed like an
+    /// expired milestone when all of it is refunded.
+    /// Emits `("resolved", id)` with `(milestone_id, status, student_amount, donor_amount)`
+    pub fn resolve_dispute(
+        env: Env,
+        arbitrator: Address,
+        scholarship_id: u64,
+        milestone_id: u32,
+        student_amount: i128,
+    ) -> Result<DisputeStatus, Error> {
+        arbitrator.require_auth();
+
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        if Self::get_arbitrator(env.clone())? != arbitrator {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut dispute = Self::open_dispute_of(&env, scholarship_id, milestone_id)?;
+        if student_amount < 0 || student_amount > dispute.amount {
+            return Err(Error::InvalidAmount);
+        }
+        let donor_amount = dispute.amount - student_amount;
+
+        dispute.status = if donor_amount == 0 {
+            DisputeStatus::Paid
+        } else if student_amount == 0 {
+            DisputeStatus::Refunded
+        } else {
+            DisputeStatus::Split
+        };
+
+        // Settle the milestone
+        let mut milestones = Self::get_milestones(env.clone(), scholarship_id)?;
+        for i in 0..milestones.len() {
+            let mut milestone = milestones.get(i).unwrap();
+            if milestone.id == milestone_id {
+                if student_amount > 0 {
+                    milestone.is_completed = true;
+                } else {
+                    milestone.is_expired = true;
+                }
+                milestones.set(i, milestone);
+                break;
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Milestones(scholarship_id), &milestones);
+
+        let balance = Self::milestone_balance(&env, scholarship_id);
+        env.storage().persistent().set(
+            &DataKey::Balance(scholarship_id),
+            &(balance - dispute.amount),
+        );
+
+        scholarship.frozen_amount -= dispute.amount;
+        scholarship.released_amount += student_amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        let key = DataKey::Dispute(scholarship_id, milestone_id);
+        env.storage().persistent().set(&key, &dispute);
+        Self::extend_persistent(&env, &key);
+
+        let token = token::Client::new(&env, &scholarship.token);
+        if student_amount > 0 {
+            token.transfer(
+                &env.current_contract_address(),
+                &scholarship.student,
+                &student_amount,
+            );
+        }
+        if donor_amount > 0 {
+            Self::refund_pro_rata(&env, &scholarship, donor_amount);
+        }
+
+        env.events().publish(
+            (symbol_short!("resolved"), scholarship_id),
+            (milestone_id, dispute.status, student_amount, donor_amount),
+        );
+
+        Ok(dispute.status)
+    }
+
+    /// Close a dispute the arbitrator did not resolve before its deadline (anyone)
+    /// Unfreezes the reward; the milestone can be completed but not disputed again.
+    /// If the scholarship was cancelled meanwhile, the reward is refunded to the contributors.
+    /// Emits `("disp_exp", id)` with `milestone_id`
+    pub fn expire_dispute(env: Env, scholarship_id: u64, milestone_id: u32) -> Result<(), Error> {
+        let mut scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        let mut dispute = Self::open_dispute_of(&env, scholarship_id, milestone_id)?;
+        if env.ledger().timestamp() <= dispute.deadline {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        dispute.status = DisputeStatus::Expired;
+        let key = DataKey::Dispute(scholarship_id, milestone_id);
+        env.storage().persistent().set(&key, &dispute);
+        Self::extend_persistent(&env, &key);
+
+        scholarship.frozen_amount -= dispute.amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        if !scholarship.is_active {
+            Self::refund_balance(&env, &scholarship);
+        }
+
+        env.events()
+            .publish((symbol_short!("disp_exp"), scholarship_id), milestone_id);
+
+        Ok(())
+    }
+
+    /// Get the dispute over a milestone, if one was ever opened
+    pub fn get_dispute(env: Env, scholarship_id: u64, milestone_id: u32) -> Option<Dispute> {
+        let key = DataKey::Dispute(scholarship_id, milestone_id);
+        let dispute = env.storage().persistent().get(&key);
+        if dispute.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        dispute
+    }
+
+    /// Get the evidence kept for a completed milestone
+    /// Milestones settled by a dispute ruling have none; their `Dispute` holds the reason hash
+    pub fn get_milestone_completion(
+        env: Env,
+        scholarship_id: u64,
+        milestone_id: u32,
+    ) -> Option<MilestoneCompletion> {
+        let key = DataKey::Completion(scholarship_id, milestone_id);
+        let completion = env.storage().persistent().get(&key);
+        if completion.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        completion
+    }
+
+    /// Register an institution's ed25519 public key for signing attestations (admin only)
+    /// Emits `("inst_add", public_key)`
+    pub fn add_institution(env: Env, admin: Address, public_key: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Institution(public_key.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::InstitutionAlreadyExists);
+        }
+
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("inst_add"), public_key), ());
+
+        Ok(())
+    }
+
+    /// Remove an institution key (admin only)
+    /// Attestations it signed can no longer be used
+    /// Emits `("inst_rm", public_key)`
+    pub fn remove_institution(
+        env: Env,
+        admin: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Institution(public_key.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::InstitutionNotFound);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("inst_rm"), public_key), ());
+
+        Ok(())
+    }
+
+    /// Check if an ed25519 public key is a registered institution
+    pub fn is_institution(env: Env, public_key: BytesN<32>) -> bool {
+        let key = DataKey::Institution(public_key);
+        let registered = env.storage().persistent().has(&key);
+        if registered {
+            Self::extend_persistent(&env, &key);
+        }
+        registered
+    }
+
+    /// Accept attestations signed by a registered institution key on a scholarship
+    /// (donor only)
+    /// Fails with an `Error` or, for a key already on the list, an `AttesterError`
+    /// Emits `("att_add", id)` with `public_key`
+    pub fn add_attester(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+        public_key: BytesN<32>,
+    ) -> Result<(), soroban_sdk::Error> {
+        donor.require_auth();
+
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor.into());
+        }
+        if !Self::is_institution(env.clone(), public_key.clone()) {
+            return Err(Error::InstitutionNotFound.into());
+        }
+
+        let mut attesters = Self::get_attesters(env.clone(), scholarship_id);
+        if attesters.contains(&public_key) {
+            return Err(AttesterError::AttesterAlreadyExists.into());
+        }
+
+        let key = DataKey::Attesters(scholarship_id);
+        attesters.push_back(public_key.clone());
+        env.storage().persistent().set(&key, &attesters);
+        Self::extend_persistent(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("att_add"), scholarship_id), public_key);
+
+        Ok(())
+    }
+
+    /// Stop accepting attestations from an institution key on a scholarship (donor only)
+    /// Fails with an `Error` or, for a key not on the list, an `AttesterError`
+    /// Emits `("att_rm", id)` with `public_key`
+    pub fn remove_attester(
+        env: Env,
+        donor: Address,
+        scholarship_id: u64,
+        public_key: BytesN<32>,
+    ) -> Result<(), soroban_sdk::Error> {
+        donor.require_auth();
+
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+        if scholarship.donor != donor {
+            return Err(Error::NotDonor.into());
+        }
+
+        let mut attesters = Self::get_attesters(env.clone(), scholarship_id);
+        let index = attesters
+            .first_index_of(&public_key)
+            .ok_or(AttesterError::AttesterNotFound)?;
+
+        attesters.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attesters(scholarship_id), &attesters);
+
+        env.events()
+            .publish((symbol_short!("att_rm"), scholarship_id), public_key);
+
+        Ok(())
+    }
+
+    /// Get the institution keys a scholarship accepts attestations from
+    pub fn get_attesters(env: Env, scholarship_id: u64) -> Vec<BytesN<32>> {
+        let key = DataKey::Attesters(scholarship_id);
+        let attesters = env.storage().persistent().get(&key);
+        if attesters.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        attesters.unwrap_or(Vec::new(&env))
+    }
+
+    /// Complete a milestone with an attestation signed by an institution on its attesters
+    /// A bad signature traps in `ed25519_verify` instead of returning an `Error`
+    pub fn complete_milestone_attested(
+        env: Env,
+        attestation: Attestation,
+        public_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<i128, Error> {
+        if !Self::is_institution(env.clone(), public_key.clone()) {
+            return Err(Error::InstitutionNotFound);
+        }
+        if env.ledger().timestamp() > attestation.expiry {
+            return Err(Error::AttestationExpired);
+        }
+
+        let message = (env.current_contract_address(), attestation.clone()).to_xdr(&env);
+        let proof_hash = env.crypto().sha256(&message).to_bytes();
+        env.crypto()
+            .ed25519_verify(&public_key, &message, &signature);
+
+        let scholarship_id = attestation.scholarship_id;
+        let milestone_id = attestation.milestone_id;
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+        if !Self::get_attesters(env.clone(), scholarship_id).contains(&public_key) {
+            return Err(Error::NotVerifier);
+        }
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+        if attestation.student != scholarship.student {
+            return Err(Error::Unauthorized);
+        }
+
+        if Self::get_milestone_completion(env.clone(), scholarship_id, milestone_id)
+            .is_some_and(|completion| completion.proof_hash == proof_hash)
+        {
+            return Err(Error::AttestationUsed);
+        }
+        Self::find_open_milestone(&env, &scholarship, milestone_id)?;
+        let verifiers = Self::get_verifiers(env.clone(), scholarship_id)?;
+        let approvals = Self::count_approvals(&env, &verifiers, scholarship_id, milestone_id, None);
+        if approvals + 1 < scholarship.quorum {
+            return Err(Error::QuorumNotReached);
+        }
+
+        let released = Self::release_milestone(
+            &env,
+            scholarship,
+            Completer::Attester(public_key.clone()),
+            milestone_id,
+            proof_hash,
+            Some(attestation.score),
+            Some(attestation.score),
+        )?;
+
+        env.events().publish(
+            (symbol_short!("attested"), scholarship_id),
+            (milestone_id, public_key, attestation.score),
+        );
+
+        Ok(released)
+    }
+
+    /// Complete a course milestone with an exam result from a batch `institution` posted
+    /// to the progress contract; anyone can submit it
+    pub fn complete_milestone_with_result(
+        env: Env,
+        institution: Address,
+        scholarship_id: u64,
+        milestone_id: u32,
+        batch_id: u32,
+        score: u32,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<i128, Error> {
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(&env);
+
+        let verifiers = Self::get_verifiers(env.clone(), scholarship_id)?;
+        if !verifiers.contains(&institution) {
+            return Err(Error::NotVerifier);
+        }
+
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+
+        // Results are per course, so the milestone has to name one
+        let milestone = Self::find_open_milestone(&env, &scholarship, milestone_id)?;
+        let course_id = milestone.course_id.ok_or(Error::InvalidProof)?;
+        let approvals = Self::count_approvals(
+            &env,
+            &verifiers,
+            scholarship_id,
+            milestone_id,
+            Some(&institution),
+        );
+        if approvals + 1 < scholarship.quorum {
+            return Err(Error::QuorumNotReached);
+        }
+
+        let result = ExamResult {
+            student: scholarship.student.clone(),
+            course_id,
+            score,
+        };
+        let leaf = env.crypto().sha256(&result.clone().to_xdr(&env)).to_bytes();
+
+        let progress_contract = Self::get_progress_contract(env.clone())?;
+        let verified = ProgressClient::new(&env, &progress_contract).try_verify_result(
+            &batch_id,
+            &institution,
+            &result,
+            &proof,
+        );
+        if verified != Ok(Ok(true)) {
+            return Err(Error::InvalidProof);
+        }
+
+        let released = Self::release_milestone(
+            &env,
+            scholarship,
+            Completer::Verifier(institution.clone()),
+            milestone_id,
+            leaf,
+            Some(score),
+            Some(score),
+        )?;
+
+        env.events().publish(
+            (symbol_short!("result"), scholarship_id),
+            (milestone_id, institution, batch_id, score),
+        );
+
+        Ok(released)
+    }
+
+    /// Get every dispute opened on a scholarship, in milestone order
+    pub fn get_disputes(env: Env, scholarship_id: u64) -> Result<Vec<Dispute>, Error> {
+        let milestones = Self::get_milestones(env.clone(), scholarship_id)?;
+        let mut disputes = Vec::new(&env);
+        for milestone in milestones.iter() {
+            if let Some(dispute) = Self::get_dispute(env.clone(), scholarship_id, milestone.id) {
+                disputes.push_back(dispute);
+            }
+        }
+        Ok(disputes)
+    }
+}
+
+impl ScholarshipEscrow {
+    /// Make sure the progress contract accepts payout records from this escrow
+    fn check_registered(env: &Env, progress_contract: &Address) -> Result<(), Error> {
+        let registered = ProgressClient::new(env, progress_contract)
+            .try_is_escrow(&env.current_contract_address());
+        if !matches!(registered, Ok(Ok(true))) {
+            return Err(Error::EscrowNotRegistered);
+        }
+        Ok(())
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored_admin != *admin {
+            return Err(Error::NotAdmin);
+        }
+        Ok(())
+    }
+
+    /// A new, active scholarship under the next id
+    fn new_scholarship(
+        env: &Env,
+        donor: Address,
+        student: Address,
+        total_amount: i128,
+        token: Address,
+        token_type: String,
+        end_date: Option<u64>,
+    ) -> Scholarship {
+        Scholarship {
+            id: Self::get_scholarship_count(env.clone()) + 1,
+            donor,
+            student,
+            total_amount,
+            released_amount: 0,
+            token,
+            token_type,
+            is_active: true,
+            frozen_amount: 0,
+            end_date,
+            quorum: 1,
+            // Enough for MAX_CONTRIBUTORS deposits to cover the total
+            min_contribution: ((total_amount + MAX_CONTRIBUTORS as i128 - 1)
+                / MAX_CONTRIBUTORS as i128)
+                .max(1),
+            matching_pool: None,
+            created_at: env.ledger().timestamp(),
+        }
+    }
+
+    /// Store a new scholarship with an empty balance and index it by student and donor
+    fn store_scholarship(
+        env: &Env,
+        scholarship: &Scholarship,
+        milestones: &Vec<Milestone>,
+        verifiers: &Vec<Address>,
+    ) -> u64 {
+        let scholarship_id = scholarship.id;
+
+        // Store scholarship
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), scholarship);
+
+        // Store milestones
+        env.storage()
+            .persistent()
+            .set(&DataKey::Milestones(scholarship_id), milestones);
+
+        // Store verifiers
+        env.storage()
+            .persistent()
+            .set(&DataKey::Verifiers(scholarship_id), verifiers);
+
+        // Initialize balance to 0
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(scholarship_id), &0_i128);
+        env.storage().persistent().set(
+            &DataKey::Contributions(scholarship_id),
+            &Vec::<Contribution>::new(env),
+        );
+
+        // Update counter
+        env.storage()
+            .instance()
+            .set(&DataKey::ScholarshipCounter, &scholarship_id);
+
+        Self::extend_scholarship(env, scholarship_id);
+        Self::extend_instance(env);
+
+        // Index by student and donor, keeping the positions for `bump`
+        let student_slot = Self::append_index(
+            env,
+            DataKey::StudentScholarshipCount(scholarship.student.clone()),
+            |i| DataKey::StudentScholarship(scholarship.student.clone(), i),
+            scholarship_id,
+        );
+        let donor_slot = Self::append_index(
+            env,
+            DataKey::DonorScholarshipCount(scholarship.donor.clone()),
+            |i| DataKey::DonorScholarship(scholarship.donor.clone(), i),
+            scholarship_id,
+        );
+        let slots_key = DataKey::IndexSlots(scholarship_id);
+        env.storage()
+            .persistent()
+            .set(&slots_key, &(student_slot, donor_slot));
+        Self::extend_persistent(env, &slots_key);
+
+        env.events().publish(
+            (symbol_short!("created"), scholarship_id),
+            (
+                scholarship.donor.clone(),
+                scholarship.student.clone(),
+                scholarship.total_amount,
+                scholarship.token.clone(),
+            ),
+        );
+
+        scholarship_id
+    }
+
+    fn validate_milestones(
+        milestones: &Vec<Milestone>,
+        total_amount: i128,
+        now: u64,
+        end_date: Option<u64>,
+    ) -> Result<(), Error> {
+        if end_date.is_some_and(|end| end <= now) {
+            return Err(Error::InvalidDeadline);
+        }
+
+        if milestones.is_empty() {
+            return Err(Error::NoMilestones);
+        }
+
+        if milestones.len() > MAX_MILESTONES {
+            return Err(Error::TooManyMilestones);
+        }
+
+        let mut total_rewards = 0_i128;
+        for (i, milestone) in milestones.iter().enumerate() {
+            if milestone.reward_amount <= 0 {
+                return Err(Error::InvalidReward);
+            }
+
+            if milestone.is_completed {
+                return Err(Error::MilestoneAlreadyCompleted);
+            }
+
+            if milestone.is_expired {
+                return Err(Error::MilestoneExpired);
+            }
+
+            if let Some(deadline) = milestone.deadline {
+                if deadline <= now || end_date.is_some_and(|end| deadline > end) {
+                    return Err(Error::InvalidDeadline);
+                }
+            }
+
+            for other in milestones.iter().skip(i + 1) {
+                if other.id == milestone.id {
+                    return Err(Error::DuplicateMilestoneId);
+                }
+            }
+
+            total_rewards = total_rewards
+                .checked_add(milestone.reward_amount)
+                .ok_or(Error::RewardsExceedTotal)?;
+        }
+
+        if total_rewards > total_amount {
+            return Err(Error::RewardsExceedTotal);
+        }
+
+        Ok(())
+    }
+
+    /// Append `scholarship_id` to a counter-indexed list, returning its position
+    fn append_index(
+        env: &Env,
+        count_key: DataKey,
+        entry_key: impl Fn(u32) -> DataKey,
+        scholarship_id: u64,
+    ) -> u32 {
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let entry_key = entry_key(count);
+        env.storage().persistent().set(&entry_key, &scholarship_id);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        Self::extend_persistent(env, &entry_key);
+        Self::extend_persistent(env, &count_key);
+        count
+    }
+
+    /// Read scholarships for index entries `start..end`
+    fn read_index(
+        env: &Env,
+        start: u32,
+        end: u32,
+        entry_key: impl Fn(u32) -> DataKey,
+    ) -> Vec<Scholarship> {
+        let mut result = Vec::new(env);
+        for i in start..end {
+            let key = entry_key(i);
+            let scholarship_id: u64 = env.storage().persistent().get(&key).unwrap();
+            Self::extend_persistent(env, &key);
+            if let Ok(scholarship) = Self::get_scholarship(env.clone(), scholarship_id) {
+                result.push_back(scholarship);
+            }
+        }
+        result
+    }
+
+    /// Clamp `offset`/`limit` to an index range of `count` entries
+    fn page_bounds(offset: u32, limit: u32, count: u32) -> (u32, u32) {
+        let start = offset.min(count);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        (start, end)
+    }
+
+    fn extend_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        env.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Extend every persistent entry of a scholarship together
+    fn extend_scholarship(env: &Env, scholarship_id: u64) {
+        Self::extend_persistent(env, &DataKey::Scholarship(scholarship_id));
+        Self::extend_persistent(env, &DataKey::Milestones(scholarship_id));
+        Self::extend_persistent(env, &DataKey::Balance(scholarship_id));
+        Self::extend_persistent(env, &DataKey::Verifiers(scholarship_id));
+        Self::extend_persistent(env, &DataKey::Contributions(scholarship_id));
+        for key in [
+            DataKey::IndexSlots(scholarship_id),
+            DataKey::Stream(scholarship_id),
+            DataKey::Stipend(scholarship_id),
+            DataKey::StipendBalance(scholarship_id),
+            DataKey::Attesters(scholarship_id),
+        ] {
+            if env.storage().persistent().has(&key) {
+                Self::extend_persistent(env, &key);
+            }
+        }
+    }
+
+    /// Send whatever is left in a scholarship's escrow back to its contributors,
+    /// keeping rewards frozen by open disputes
+    fn refund_balance(env: &Env, scholarship: &Scholarship) -> i128 {
+        let balance = Self::milestone_balance(env, scholarship.id);
+        let stipend = Self::stipend_balance(env, scholarship.id);
+        let refund = (balance - scholarship.frozen_amount).max(0) + stipend;
+
+        if refund > 0 {
+            env.storage().persistent().set(
+                &DataKey::Balance(scholarship.id),
+                &balance.min(scholarship.frozen_amount),
+            );
+            if stipend > 0 {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::StipendBalance(scholarship.id), &0_i128);
+            }
+
+            Self::refund_pro_rata(env, scholarship, refund);
+        }
+
+        refund
+    }
+
+    fn milestone_balance(env: &Env, scholarship_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Balance(scholarship_id))
+            .unwrap_or(0)
+    }
+
+    fn stipend_balance(env: &Env, scholarship_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StipendBalance(scholarship_id))
+            .unwrap_or(0)
+    }
+
+    /// Split incoming funds between the pools: the stipend pool takes what the unpaid
+    /// stipend periods still need, the milestone pool the rest
+    fn credit_pools(env: &Env, scholarship_id: u64, amount: i128) {
+        let stipend_balance = Self::stipend_balance(env, scholarship_id);
+        let to_stipend = match Self::get_stipend(env.clone(), scholarship_id) {
+            Ok(stipend) => {
+                let owed = stipend.amount * (stipend.periods - stipend.paid_periods) as i128;
+                amount.min(owed - stipend_balance).max(0)
+            }
+            Err(_) => 0,
+        };
+
+        if to_stipend > 0 {
+            env.storage().persistent().set(
+                &DataKey::StipendBalance(scholarship_id),
+                &(stipend_balance + to_stipend),
+            );
+        }
+        let balance = Self::milestone_balance(env, scholarship_id);
+        env.storage().persistent().set(
+            &DataKey::Balance(scholarship_id),
+            &(balance + amount - to_stipend),
+        );
+    }
+
+    /// Number of stipend periods due at the current time
+    fn due_periods(env: &Env, stipend: &Stipend) -> u32 {
+        let now = env.ledger().timestamp();
+        if now < stipend.start {
+            return 0;
+        }
+        let due = (now - stipend.start) / stipend.period + 1;
+        due.min(stipend.periods as u64) as u32
+    }
+
+    /// Pay the student every due stipend period the stipend pool can cover in full
+    /// Updates the stipend and its pool; the caller stores `scholarship`. Returns the amount paid.
+    fn settle_stipend(env: &Env, scholarship: &mut Scholarship) -> i128 {
+        let key = DataKey::Stipend(scholarship.id);
+        let Some(mut stipend) = env.storage().persistent().get::<_, Stipend>(&key) else {
+            return 0;
+        };
+
+        let pool = Self::stipend_balance(env, scholarship.id);
+        let funded = (pool / stipend.amount) as u32;
+        let periods = (Self::due_periods(env, &stipend) - stipend.paid_periods).min(funded);
+        if periods == 0 {
+            return 0;
+        }
+
+        let amount = stipend.amount * periods as i128;
+        stipend.paid_periods += periods;
+        env.storage().persistent().set(&key, &stipend);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StipendBalance(scholarship.id), &(pool - amount));
+        scholarship.released_amount += amount;
+
+        token::Client::new(env, &scholarship.token).transfer(
+            &env.current_contract_address(),
+            &scholarship.student,
+            &amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("stipend"), scholarship.id),
+            (scholarship.student.clone(), amount, stipend.paid_periods),
+        );
+
+        amount
+    }
+
+    /// Vested part of a stream at the current time, with paused time taken out
+    fn vested_amount(env: &Env, stream: &Stream) -> i128 {
+        let now = stream.paused_at.unwrap_or(env.ledger().timestamp());
+        let elapsed = now.saturating_sub(stream.paused_for);
+        if elapsed < stream.cliff {
+            0
+        } else if elapsed >= stream.end {
+            stream.amount
+        } else {
+            stream.amount * (elapsed - stream.start) as i128 / (stream.end - stream.start) as i128
+        }
+    }
+
+    /// Pay the student what has vested and not been withdrawn, up to the unfrozen balance
+    /// Updates the stream and balance; the caller stores `scholarship`. Returns the amount paid.
+    fn settle_stream(env: &Env, scholarship: &mut Scholarship) -> i128 {
+        let key = DataKey::Stream(scholarship.id);
+        let Some(mut stream) = env.storage().persistent().get::<_, Stream>(&key) else {
+            return 0;
+        };
+
+        let balance = Self::milestone_balance(env, scholarship.id);
+        let amount = (Self::vested_amount(env, &stream) - stream.withdrawn)
+            .min(balance - scholarship.frozen_amount)
+            .max(0);
+        if amount == 0 {
+            return 0;
+        }
+
+        stream.withdrawn += amount;
+        env.storage().persistent().set(&key, &stream);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(scholarship.id), &(balance - amount));
+        scholarship.released_amount += amount;
+
+        token::Client::new(env, &scholarship.token).transfer(
+            &env.current_contract_address(),
+            &scholarship.student,
+            &amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("vested"), scholarship.id),
+            (scholarship.student.clone(), amount, stream.withdrawn),
+        );
+
+        amount
+    }
+
+    /// Read a stream for a donor-only call
+    fn donor_stream(env: &Env, donor: &Address, scholarship_id: u64) -> Result<Stream, Error> {
+        donor.require_auth();
+
+        let scholarship = Self::get_scholarship(env.clone(), scholarship_id)?;
+        Self::extend_instance(env);
+
+        if &scholarship.donor != donor {
+            return Err(Error::NotDonor);
+        }
+        if !scholarship.is_active {
+            return Err(Error::ScholarshipNotActive);
+        }
+        Self::get_stream(env.clone(), scholarship_id)
+    }
+
+    /// Add `amount` to a contributor's share, making room for a new contributor
+    fn add_contribution(
+        contributions: &mut Vec<Contribution>,
+        contributor: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        match contributions
+            .iter()
+            .position(|share| &share.contributor == contributor)
+        {
+            Some(i) => {
+                let mut share = contributions.get(i as u32).unwrap();
+                share.amount += amount;
+                contributions.set(i as u32, share);
+            }
+            None => {
+                if contributions.len() >= MAX_CONTRIBUTORS {
+                    return Err(Error::TooManyContributors);
+                }
+                contributions.push_back(Contribution {
+                    contributor: contributor.clone(),
+                    amount,
+                    refunded: 0,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Match a deposit from a matching pool, within its ratio, its per-scholarship cap
+    /// and what is left in it. The sponsor's own deposits and deposits after the pool's
+    /// end date are not matched, nor are deposits while the sponsor has no share yet and
+    /// every contributor slot is taken. Returns the matched amount.
+    fn match_deposit(
+        env: &Env,
+        pool_id: u32,
+        scholarship_id: u64,
+        contributions: &mut Vec<Contribution>,
+        contributor: &Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let Ok(mut pool) = Self::get_matching_pool(env.clone(), pool_id) else {
+            return Ok(0);
+        };
+        if &pool.sponsor == contributor || env.ledger().timestamp() > pool.end_date {
+            return Ok(0);
+        }
+        // A sponsor without a share can't take the last contributor slot from depositors
+        if contributions.len() >= MAX_CONTRIBUTORS
+            && !contributions
+                .iter()
+                .any(|share| share.contributor == pool.sponsor)
+        {
+            return Ok(0);
+        }
+
+        let key = DataKey::PoolMatched(pool_id, scholarship_id);
+        let already: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let matched = (amount * pool.ratio_bps as i128 / BPS_DENOMINATOR)
+            .min(pool.cap - already)
+            .min(pool.remaining);
+        if matched <= 0 {
+            return Ok(0);
+        }
+        Self::add_contribution(contributions, &pool.sponsor, matched)?;
+
+        pool.remaining -= matched;
+        pool.matched += matched;
+        env.storage()
+            .persistent()
+            .set(&DataKey::MatchingPool(pool_id), &pool);
+        env.storage().persistent().set(&key, &(already + matched));
+        Self::extend_persistent(env, &key);
+
+        Ok(matched)
+    }
+
+    /// Pay `amount` out of escrow back to the contributors in proportion to what each
+    /// deposited; the rounding remainder is spread one unit each over the first
+    /// contributors, never past what a contributor still has at stake
+    fn refund_pro_rata(env: &Env, scholarship: &Scholarship, amount: i128) {
+        let key = DataKey::Contributions(scholarship.id);
+        let mut contributions: Vec<Contribution> = env.storage().persistent().get(&key).unwrap();
+        // Weigh by what each contributor still has in escrow, net of earlier refunds
+        let stake = |share: &Contribution| share.amount - share.refunded;
+        let total: i128 = contributions.iter().map(|share| stake(&share)).sum();
+        if total <= 0 {
+            return;
+        }
+
+        let mut parts = Vec::new(env);
+        let mut remainder = amount;
+        for share in contributions.iter() {
+            let part = amount * stake(&share) / total;
+            remainder -= part;
+            parts.push_back(part);
+        }
+        // Less than one unit short per contributor, and every rounded-down part is
+        // below its stake
+        for i in 0..parts.len() {
+            if remainder == 0 {
+                break;
+            }
+            let part = parts.get(i).unwrap();
+            if part < stake(&contributions.get(i).unwrap()) {
+                parts.set(i, part + 1);
+                remainder -= 1;
+            }
+        }
+
+        let token = token::Client::new(env, &scholarship.token);
+        for i in 0..contributions.len() {
+            let part = parts.get(i).unwrap();
+            if part == 0 {
+                continue;
+            }
+            let mut share = contributions.get(i).unwrap();
+            token.transfer(&env.current_contract_address(), &share.contributor, &part);
+            share.refunded += part;
+            contributions.set(i, share);
+        }
+        env.storage().persistent().set(&key, &contributions);
+    }
+
+    /// Mark a milestone completed and pay its reward to the student, once the student's
+    /// progress reaches `required_progress` and the escrow holds enough unfrozen funds
+    /// The `("completed", id)` event names the escrow as verifier for attestations
+    fn release_milestone(
+        env: &Env,
+        mut scholarship: Scholarship,
+        completed_by: Completer,
+        milestone_id: u32,
+        proof_hash: BytesN<32>,
+        score: Option<u32>,
+        proven_progress: Option<u32>,
+    ) -> Result<i128, Error> {
+        let scholarship_id = scholarship.id;
+
+        // Get milestones
+        let mut milestones = Self::get_milestones(env.clone(), scholarship_id)?;
+
+        // Find and update milestone
+        let mut milestone_found = false;
+        let mut reward_amount = 0_i128;
+        let mut required_progress = 0_u32;
+        let mut course_id = None;
+
+        for i in 0..milestones.len() {
+            let mut milestone = milestones.get(i).unwrap();
+            if milestone.id == milestone_id {
+                if milestone.is_completed {
+                    return Err(Error::MilestoneAlreadyCompleted);
+                }
+                if Self::is_expired(env, &scholarship, &milestone) {
+                    return Err(Error::MilestoneExpired);
+                }
+                milestone.is_completed = true;
+                reward_amount = milestone.reward_amount;
+                required_progress = milestone.required_progress;
+                course_id = milestone.course_id;
+                milestones.set(i, milestone);
+                milestone_found = true;
+                break;
+            }
+        }
+
+        if !milestone_found {
+            return Err(Error::MilestoneNotFound);
+        }
+
+        if Self::has_open_dispute(env, scholarship_id, milestone_id) {
+            return Err(Error::MilestoneDisputed);
+        }
+
+        // Check the student's reported progress, or the proven or attested score
+        if required_progress > 0 {
+            let progress_contract = Self::get_progress_contract(env.clone())?;
+            let progress_client = ProgressClient::new(env, &progress_contract);
+            let progress = match (proven_progress, course_id) {
+                (Some(progress), _) => progress,
+                (None, Some(course_id)) => {
+                    progress_client.get_course_progress(&scholarship.student, &course_id)
+                }
+                (None, None) => progress_client
+                    .get_student_info(&scholarship.student)
+                    .map(|info| info.total_progress)
+                    .unwrap_or(0),
+            };
+            if progress < required_progress {
+                return Err(Error::ProgressNotReached);
+            }
+        }
+
+        // Check if enough funds deposited, leaving funds frozen by disputes alone
+        let current_balance = Self::milestone_balance(env, scholarship_id);
+
+        if current_balance - scholarship.frozen_amount < reward_amount {
+            return Err(Error::InsufficientFunds);
+        }
+
+        // Update scholarship
+        scholarship.released_amount += reward_amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+
+        // Update milestones
+        env.storage()
+            .persistent()
+            .set(&DataKey::Milestones(scholarship_id), &milestones);
+
+        // Update balance
+        env.storage().persistent().set(
+            &DataKey::Balance(scholarship_id),
+            &(current_balance - reward_amount),
+        );
+
+        // Pay the student from escrow
+        token::Client::new(env, &scholarship.token).transfer(
+            &env.current_contract_address(),
+            &scholarship.student,
+            &reward_amount,
+        );
+
+        // Lock the reported progress this payout relied on against later revocation
+        if required_progress > 0 && proven_progress.is_none() {
+            let progress_contract = Self::get_progress_contract(env.clone())?;
+            let recorded = ProgressClient::new(env, &progress_contract).try_record_payout(
+                &env.current_contract_address(),
+                &scholarship.student,
+                &course_id,
+                &required_progress,
+            );
+            if !matches!(recorded, Ok(Ok(()))) {
+                return Err(Error::EscrowNotRegistered);
+            }
+        }
+
+        let key = DataKey::Completion(scholarship_id, milestone_id);
+        env.storage().persistent().set(
+            &key,
+            &MilestoneCompletion {
+                proof_hash,
+                completed_by: completed_by.clone(),
+                completed_at: env.ledger().timestamp(),
+                score,
+            },
+        );
+        Self::extend_persistent(env, &key);
+
+        let verifier = match completed_by {
+            Completer::Verifier(verifier) => verifier,
+            Completer::Attester(_) => env.current_contract_address(),
+        };
+
+        env.events().publish(
+            (symbol_short!("completed"), scholarship_id),
+            (milestone_id, verifier, scholarship.student, reward_amount),
+        );
+
+        Ok(reward_amount)
+    }
+
+    /// SHA-256 of a proof string's bytes, cut out of its XDR encoding behind the
+    /// 4-byte type tag and 4-byte length
+    fn hash_proof(env: &Env, proof_data: &String) -> BytesN<32> {
+        let xdr = proof_data.clone().to_xdr(env);
+        env.crypto()
+            .sha256(&xdr.slice(8..8 + proof_data.len()))
+            .to_bytes()
+    }
+
+    /// Find a milestone that can still be completed
+    fn find_open_milestone(
+        env: &Env,
+        scholarship: &Scholarship,
+        milestone_id: u32,
+    ) -> Result<Milestone, Error> {
+        let milestone = Self::get_milestones(env.clone(), scholarship.id)?
+            .iter()
+            .find(|milestone| milestone.id == milestone_id)
+            .ok_or(Error::MilestoneNotFound)?;
+        if milestone.is_completed {
+            return Err(Error::MilestoneAlreadyCompleted);
+        }
+        if Self::is_expired(env, scholarship, &milestone) {
+            return Err(Error::MilestoneExpired);
+        }
+        Ok(milestone)
+    }
+
+    /// Count approvals of a milestone by current verifiers other than `except`
+    fn count_approvals(
+        env: &Env,
+        verifiers: &Vec<Address>,
+        scholarship_id: u64,
+        milestone_id: u32,
+        except: Option<&Address>,
+    ) -> u32 {
+        Self::get_approvals(env.clone(), scholarship_id, milestone_id)
+            .iter()
+            .filter(|approver| Some(approver) != except && verifiers.contains(approver))
+            .count() as u32
+    }
+
+    /// A milestone's deadline, pulled in to the scholarship's end date
+    fn effective_deadline(scholarship: &Scholarship, milestone: &Milestone) -> Option<u64> {
+        match (milestone.deadline, scholarship.end_date) {
+            (Some(deadline), Some(end)) => Some(deadline.min(end)),
+            (deadline, end) => deadline.or(end),
+        }
+    }
+
+    /// Whether a milestone expired or its deadline passed
+    fn is_expired(env: &Env, scholarship: &Scholarship, milestone: &Milestone) -> bool {
+        milestone.is_expired
+            || Self::effective_deadline(scholarship, milestone)
+                .is_some_and(|deadline| env.ledger().timestamp() > deadline)
+    }
+
+    fn has_open_dispute(env: &Env, scholarship_id: u64, milestone_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, Dispute>(&DataKey::Dispute(scholarship_id, milestone_id))
+            .is_some_and(|dispute| dispute.status == DisputeStatus::Open)
+    }
+
+    /// Read an open dispute, failing when there is none
+    fn open_dispute_of(
+        env: &Env,
+        scholarship_id: u64,
+        milestone_id: u32,
+    ) -> Result<Dispute, Error> {
+        let dispute = Self::get_dispute(env.clone(), scholarship_id, milestone_id)
+            .ok_or(Error::DisputeNotFound)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::DisputeNotOpen);
+        }
+        Ok(dispute)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use scholarship_milestone::{
+        Error as ProgressError, ReasonCode, ScholarshipMilestone, ScholarshipMilestoneClient,
+    };
+    use scholarship_token::{ScholarshipToken, ScholarshipTokenClient};
+    use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+    use soroban_sdk::testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger,
+    };
+    use soroban_sdk::{token, vec, Bytes, BytesN, IntoVal, Symbol, Val};
+
+    fn create_milestone(env: &Env, id: u32, reward_amount: i128) -> Milestone {
+        Milestone {
+            id,
+            title: String::from_str(env, "Complete Module 1"),
+            description: String::from_str(env, "Finish first course module"),
+            required_progress: 25,
+            course_id: None,
+            reward_amount,
+            proof_type: String::from_str(env, "exam"),
+            is_completed: false,
+            deadline: None,
+            on_expiry: ExpiryRule::Reclaim,
+            is_expired: false,
+        }
+    }
+
+    fn due_milestone(
+        env: &Env,
+        id: u32,
+        reward_amount: i128,
+        deadline: u64,
+        on_expiry: ExpiryRule,
+    ) -> Milestone {
+        Milestone {
+            deadline: Some(deadline),
+            on_expiry,
+            ..create_milestone(env, id, reward_amount)
+        }
+    }
+
+    fn create_token<'a>(
+        env: &Env,
+        admin: &Address,
+    ) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
+        let sac = env.register_stellar_asset_contract_v2(admin.clone());
+        (
+            token::Client::new(env, &sac.address()),
+            token::StellarAssetClient::new(env, &sac.address()),
+        )
+    }
+
+    const COURSE: u32 = 1;
+
+    fn evidence(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[7; 32])
+    }
+
+    fn reason(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[9; 32])
+    }
+
+    fn proof(env: &Env) -> String {
+        String::from_str(env, "exam_score_85")
+    }
+
+    /// Register the progress contract with one reporter and one course, point the
+    /// escrow at it and report `progress` for `student` in that course
+    /// Returns the progress contract, the admin of both contracts and the reporter
+    fn setup_progress<'a>(
+        env: &Env,
+        client: &ScholarshipEscrowClient,
+        student: &Address,
+        progress: u32,
+    ) -> (ScholarshipMilestoneClient<'a>, Address, Address) {
+        let progress_id = env.register_contract(None, ScholarshipMilestone);
+        let progress_client = ScholarshipMilestoneClient::new(env, &progress_id);
+        let admin = Address::generate(env);
+        let reporter = Address::generate(env);
+        progress_client.initialize(&admin);
+        progress_client.add_reporter(&admin, &reporter);
+        progress_client.add_course(&admin, &COURSE, &String::from_str(env, "Algorithms"), &1);
+        progress_client.add_escrow(&admin, &client.address);
+        client.initialize(&admin, &progress_id);
+        if progress > 0 {
+            progress_client.update_progress(&reporter, student, &COURSE, &progress, &evidence(env));
+        }
+        (progress_client, admin, reporter)
+    }
+
+    /// Assert the last event published by `contract_id` itself
+    fn assert_last_event(env: &Env, contract_id: &Address, topics: Vec<Val>, data: Val) {
+        let last = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(id, _, _)| id == contract_id)
+            .last()
+            .expect("no contract event");
+        assert_eq!(
+            vec![env, last],
+            vec![env, (contract_id.clone(), topics, data)]
+        );
+    }
+
+    /// Escrow with a funded donor, one verifier and a Stellar Asset Contract token
+    struct Setup<'a> {
+        env: Env,
+        client: ScholarshipEscrowClient<'a>,
+        progress: ScholarshipMilestoneClient<'a>,
+        admin: Address,
+        reporter: Address,
+        token: token::Client<'a>,
+        donor: Address,
+        student: Address,
+        verifier: Address,
+    }
+
+    impl Setup<'_> {
+        fn new() -> Self {
+            let env = Env::default();
+            env.mock_all_auths();
+
+            let contract_id = env.register_contract(None, ScholarshipEscrow);
+            let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+            let admin = Address::generate(&env);
+            let donor = Address::generate(&env);
+            let student = Address::generate(&env);
+            let verifier = Address::generate(&env);
+            let (token, token_admin) = create_token(&env, &admin);
+            token_admin.mint(&donor, &10_000);
+
+            // Student has reached the progress every test milestone requires
+            let (progress, admin, reporter) = setup_progress(&env, &client, &student, 25);
+
+            Setup {
+                env,
+                client,
+                progress,
+                admin,
+                reporter,
+                token,
+                donor,
+                student,
+                verifier,
+            }
+        }
+
+        /// Create a scholarship whose total is the sum of the milestone rewards
+        fn create_scholarship(&self, milestones: &Vec<Milestone>) -> u64 {
+            self.create_scholarship_until(milestones, None)
+        }
+
+        /// Same as `create_scholarship`, ending at `end_date`
+        fn create_scholarship_until(
+            &self,
+            milestones: &Vec<Milestone>,
+            end_date: Option<u64>,
+        ) -> u64 {
+            let total: i128 = milestones.iter().map(|m| m.reward_amount).sum();
+            self.client.create_scholarship(
+                &self.donor,
+                &self.student,
+                &total,
+                &self.token.address,
+                &String::from_str(&self.env, "USDC"),
+                milestones,
+                &vec![&self.env, self.verifier.clone()],
+                &end_date,
+            )
+        }
+
+        fn complete(&self, scholarship_id: u64, milestone_id: u32) -> Result<i128, Error> {
+            match self.client.try_complete_milestone(
+                &self.verifier,
+                &scholarship_id,
+                &milestone_id,
+                &String::from_str(&self.env, "exam_score_85"),
+            ) {
+                Ok(released) => Ok(released.unwrap()),
+                Err(error) => Err(error.unwrap()),
+            }
+        }
+
+        /// Fund a (400, 600) scholarship with `deposit`, set an arbitrator and have the
+        /// student dispute milestone 1. Returns the scholarship id and the arbitrator.
+        fn disputed_scholarship(&self, deposit: i128) -> (u64, Address) {
+            let id = self.create_scholarship(&vec![
+                &self.env,
+                create_milestone(&self.env, 1, 400),
+                create_milestone(&self.env, 2, 600),
+            ]);
+            self.client.deposit_funds(&self.donor, &id, &deposit);
+
+            let arbitrator = Address::generate(&self.env);
+            self.client.set_arbitrator(&self.admin, &arbitrator);
+            self.client
+                .open_dispute(&self.student, &id, &1, &reason(&self.env));
+            (id, arbitrator)
+        }
+    }
+
+    #[test]
+    fn test_create_scholarship() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipEscrow);
+        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let (token, _) = create_token(&env, &donor);
+
+        let milestones = vec![&env, create_milestone(&env, 1, 250)];
+
+        env.mock_all_auths();
+        let scholarship_id = client.create_scholarship(
+            &donor,
+            &student,
+            &1000,
+            &token.address,
+            &String::from_str(&env, "BRS"),
+            &milestones,
+            &vec![&env, verifier.clone()],
+            &None,
+        );
+
+        assert_eq!(scholarship_id, 1);
+
+        let scholarship = client.get_scholarship(&scholarship_id);
+        assert_eq!(scholarship.donor, donor);
+        assert_eq!(scholarship.student, student);
+        assert_eq!(scholarship.total_amount, 1000);
+        assert_eq!(scholarship.token, token.address);
+        assert_eq!(client.get_verifiers(&scholarship_id), vec![&env, verifier]);
+    }
+
+    #[test]
+    fn test_deposit_and_complete_milestone() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipEscrow);
+        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+
+        let milestones = vec![&env, create_milestone(&env, 1, 250)];
+
+        env.mock_all_auths();
+        setup_progress(&env, &client, &student, 25);
+        token_admin.mint(&donor, &1000);
+        let scholarship_id = client.create_scholarship(
+            &donor,
+            &student,
+            &1000,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &milestones,
+            &vec![&env, verifier.clone()],
+            &None,
+        );
+
+        // Deposit funds
+        client.deposit_funds(&donor, &scholarship_id, &1000);
+        assert_eq!(client.get_balance(&scholarship_id).total, 1000);
+        assert_eq!(token.balance(&donor), 0);
+        assert_eq!(token.balance(&contract_id), 1000);
+
+        // Complete milestone
+        let proof = String::from_str(&env, "exam_score_85");
+        let released = client.complete_milestone(&verifier, &scholarship_id, &1, &proof);
+
+        // Verifier signature was required
+        assert_eq!(
+            env.auths(),
+            std::vec![(
+                verifier.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        contract_id.clone(),
+                        Symbol::new(&env, "complete_milestone"),
+                        (verifier.clone(), scholarship_id, 1_u32, proof.clone()).into_val(&env),
+                    )),
+                    sub_invocations: std::vec![],
+                }
+            )]
+        );
+
+        assert_eq!(released, 250);
+        assert_eq!(client.get_balance(&scholarship_id).total, 750);
+        assert_eq!(token.balance(&student), 250);
+        assert_eq!(token.balance(&contract_id), 750);
+
+        let scholarship = client.get_scholarship(&scholarship_id);
+        assert_eq!(scholarship.released_amount, 250);
+    }
+
+    #[test]
+    fn test_escrow_with_scholarship_token() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipEscrow);
+        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+
+        let token_id = env.register_contract(None, ScholarshipToken);
+        let token = ScholarshipTokenClient::new(&env, &token_id);
+        token.initialize(
+            &admin,
+            &String::from_str(&env, "Scholarship Token"),
+            &String::from_str(&env, "BRS"),
+        );
+
+        env.mock_all_auths();
+        setup_progress(&env, &client, &student, 25);
+        token.mint(&donor, &500);
+
+        let milestones = vec![
+            &env,
+            create_milestone(&env, 1, 200),
+            create_milestone(&env, 2, 300),
+        ];
+        let scholarship_id = client.create_scholarship(
+            &donor,
+            &student,
+            &500,
+            &token_id,
+            &String::from_str(&env, "BRS"),
+            &milestones,
+            &vec![&env, verifier.clone()],
+            &None,
+        );
+
+        client.deposit_funds(&donor, &scholarship_id, &500);
+        assert_eq!(token.balance(&donor), 0);
+        assert_eq!(token.balance(&contract_id), 500);
+
+        client.complete_milestone(
+            &verifier,
+            &scholarship_id,
+            &2,
+            &String::from_str(&env, "project_url"),
+        );
+        assert_eq!(token.balance(&student), 300);
+        assert_eq!(token.balance(&contract_id), 200);
+        assert_eq!(client.get_balance(&scholarship_id).total, 200);
+    }
+
+    #[test]
+    fn test_complete_milestone_without_deposit() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipEscrow);
+        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let (token, _) = create_token(&env, &donor);
+
+        env.mock_all_auths();
+        setup_progress(&env, &client, &student, 25);
+        let scholarship_id = client.create_scholarship(
+            &donor,
+            &student,
+            &250,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &vec![&env, create_milestone(&env, 1, 250)],
+            &vec![&env, verifier.clone()],
+            &None,
+        );
+
+        assert_eq!(
+            client.try_complete_milestone(
+                &verifier,
+                &scholarship_id,
+                &1,
+                &String::from_str(&env, "exam_score_85"),
+            ),
+            Err(Ok(Error::InsufficientFunds))
+        );
+    }
+
+    #[test]
+    fn test_complete_milestone_by_non_verifier() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipEscrow);
+        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+
+        env.mock_all_auths();
+        token_admin.mint(&donor, &250);
+        let scholarship_id = client.create_scholarship(
+            &donor,
+            &student,
+            &250,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &vec![&env, create_milestone(&env, 1, 250)],
+            &vec![&env, verifier],
+            &None,
+        );
+        client.deposit_funds(&donor, &scholarship_id, &250);
+
+        // The student cannot approve their own milestone
+        assert_eq!(
+            client.try_complete_milestone(
+                &student,
+                &scholarship_id,
+                &1,
+                &String::from_str(&env, "exam_score_85"),
+            ),
+            Err(Ok(Error::NotVerifier))
+        );
+    }
+
+    #[test]
+    fn test_manage_verifiers() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipEscrow);
+        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier1 = Address::generate(&env);
+        let verifier2 = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+
+        env.mock_all_auths();
+        setup_progress(&env, &client, &student, 25);
+        token_admin.mint(&donor, &250);
+        let scholarship_id = client.create_scholarship(
+            &donor,
+            &student,
+            &250,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &vec![&env, create_milestone(&env, 1, 250)],
+            &vec![&env, verifier1.clone()],
+            &None,
+        );
+        client.deposit_funds(&donor, &scholarship_id, &250);
+
+        client.add_verifier(&donor, &scholarship_id, &verifier2);
+        assert_eq!(
+            client.get_verifiers(&scholarship_id),
+            vec![&env, verifier1.clone(), verifier2.clone()]
+        );
+
+        client.remove_verifier(&donor, &scholarship_id, &verifier1);
+        assert_eq!(
+            client.get_verifiers(&scholarship_id),
+            vec![&env, verifier2.clone()]
+        );
+
+        // Removed verifier can no longer complete milestones
+        let proof = String::from_str(&env, "exam_score_85");
+        assert_eq!(
+            client.try_complete_milestone(&verifier1, &scholarship_id, &1, &proof),
+            Err(Ok(Error::NotVerifier))
+        );
+
+        // Last verifier cannot be removed
+        assert_eq!(
+            client.try_remove_verifier(&donor, &scholarship_id, &verifier2),
+            Err(Ok(Error::VerifierRequired))
+        );
+
+        client.complete_milestone(&verifier2, &scholarship_id, &1, &proof);
+        assert_eq!(token.balance(&student), 250);
+    }
+
+    #[test]
+    fn test_cancel_scholarship_refunds_donor() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipEscrow);
+        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+
+        env.mock_all_auths();
+        setup_progress(&env, &client, &student, 25);
+        token_admin.mint(&donor, &1000);
+        let scholarship_id = client.create_scholarship(
+            &donor,
+            &student,
+            &1000,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &vec![
+                &env,
+                create_milestone(&env, 1, 400),
+                create_milestone(&env, 2, 600),
+            ],
+            &vec![&env, verifier.clone()],
+            &None,
+        );
+        client.deposit_funds(&donor, &scholarship_id, &1000);
+
+        // Partial cancellation: completed payout stays with the student
+        client.complete_milestone(
+            &verifier,
+            &scholarship_id,
+            &1,
+            &String::from_str(&env, "exam_score_85"),
+        );
+        let refunded = client.cancel_scholarship(&donor, &scholarship_id);
+
+        assert_eq!(refunded, 600);
+        assert_eq!(token.balance(&donor), 600);
+        assert_eq!(token.balance(&student), 400);
+        assert_eq!(token.balance(&contract_id), 0);
+        assert_eq!(client.get_balance(&scholarship_id).total, 0);
+        assert!(!client.get_scholarship(&scholarship_id).is_active);
+
+        // Nothing more can be paid out or refunded
+        assert_eq!(
+            client.try_complete_milestone(
+                &verifier,
+                &scholarship_id,
+                &2,
+                &String::from_str(&env, "exam_score_90"),
+            ),
+            Err(Ok(Error::ScholarshipNotActive))
+        );
+        assert_eq!(
+            client.try_cancel_scholarship(&donor, &scholarship_id),
+            Err(Ok(Error::ScholarshipNotActive))
+        );
+    }
+
+    #[test]
+    fn test_close_scholarship_withdraws_surplus() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipEscrow);
+        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+
+        env.mock_all_auths();
+        setup_progress(&env, &client, &student, 25);
+        token_admin.mint(&donor, &1200);
+        let scholarship_id = client.create_scholarship(
+            &donor,
+            &student,
+            &1000,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &vec![&env, create_milestone(&env, 1, 1000)],
+            &vec![&env, verifier.clone()],
+            &None,
+        );
+        client.deposit_funds(&donor, &scholarship_id, &1200);
+
+        // Cannot close while a milestone is still open
+        assert_eq!(
+            client.try_close_scholarship(&donor, &scholarship_id),
+            Err(Ok(Error::MilestonesIncomplete))
+        );
+
+        client.complete_milestone(
+            &verifier,
+            &scholarship_id,
+            &1,
+            &String::from_str(&env, "exam_score_85"),
+        );
+        let withdrawn = client.close_scholarship(&donor, &scholarship_id);
+
+        assert_eq!(withdrawn, 200);
+        assert_eq!(token.balance(&donor), 200);
+        assert_eq!(token.balance(&student), 1000);
+        assert_eq!(token.balance(&contract_id), 0);
+        assert!(!client.get_scholarship(&scholarship_id).is_active);
+    }
+
+    #[test]
+    fn test_error_scholarship_not_found() {
+        let setup = Setup::new();
+
+        assert_eq!(
+            setup.client.try_get_scholarship(&42),
+            Err(Ok(Error::ScholarshipNotFound))
+        );
+        assert_eq!(
+            setup.client.try_deposit_funds(&setup.donor, &42, &100),
+            Err(Ok(Error::ScholarshipNotFound))
+        );
+        assert_eq!(setup.complete(42, 1), Err(Error::ScholarshipNotFound));
+    }
+
+    #[test]
+    fn test_error_not_donor() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        let stranger = Address::generate(&setup.env);
+
+        assert_eq!(
+            setup.client.try_set_min_contribution(&stranger, &id, &100),
+            Err(Ok(Error::NotDonor))
+        );
+        assert_eq!(
+            setup.client.try_cancel_scholarship(&stranger, &id),
+            Err(Ok(Error::NotDonor))
+        );
+        assert_eq!(
+            setup.client.try_add_verifier(&stranger, &id, &stranger),
+            Err(Ok(Error::NotDonor))
+        );
+    }
+
+    #[test]
+    fn test_error_scholarship_not_active() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        setup.client.cancel_scholarship(&setup.donor, &id);
+
+        assert_eq!(
+            setup.client.try_deposit_funds(&setup.donor, &id, &100),
+            Err(Ok(Error::ScholarshipNotActive))
+        );
+        assert_eq!(
+            setup.client.try_close_scholarship(&setup.donor, &id),
+            Err(Ok(Error::ScholarshipNotActive))
+        );
+    }
+
+    #[test]
+    fn test_error_invalid_amount() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+
+        assert_eq!(
+            setup.client.try_deposit_funds(&setup.donor, &id, &0),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(
+            setup.client.try_deposit_funds(&setup.donor, &id, &-5),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+
+    #[test]
+    fn test_error_not_verifier() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+
+        assert_eq!(
+            setup.client.try_complete_milestone(
+                &setup.donor,
+                &id,
+                &1,
+                &String::from_str(&setup.env, "exam_score_85"),
+            ),
+            Err(Ok(Error::NotVerifier))
+        );
+    }
+
+    #[test]
+    fn test_error_verifier_required() {
+        let setup = Setup::new();
+
+        assert_eq!(
+            setup.client.try_create_scholarship(
+                &setup.donor,
+                &setup.student,
+                &100,
+                &setup.token.address,
+                &String::from_str(&setup.env, "USDC"),
+                &vec![&setup.env, create_milestone(&setup.env, 1, 100)],
+                &Vec::new(&setup.env),
+                &None
+            ),
+            Err(Ok(Error::VerifierRequired))
+        );
+    }
+
+    #[test]
+    fn test_error_verifier_already_exists() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+
+        assert_eq!(
+            setup
+                .client
+                .try_add_verifier(&setup.donor, &id, &setup.verifier),
+            Err(Ok(Error::VerifierAlreadyExists))
+        );
+    }
+
+    #[test]
+    fn test_error_verifier_not_found() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        let stranger = Address::generate(&setup.env);
+
+        assert_eq!(
+            setup
+                .client
+                .try_remove_verifier(&setup.donor, &id, &stranger),
+            Err(Ok(Error::VerifierNotFound))
+        );
+    }
+
+    #[test]
+    fn test_error_milestone_not_found() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+
+        assert_eq!(setup.complete(id, 7), Err(Error::MilestoneNotFound));
+    }
+
+    #[test]
+    fn test_error_milestone_already_completed() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            create_milestone(&setup.env, 1, 100),
+            create_milestone(&setup.env, 2, 100),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &200);
+
+        assert_eq!(setup.complete(id, 1), Ok(100));
+        assert_eq!(setup.complete(id, 1), Err(Error::MilestoneAlreadyCompleted));
+    }
+
+    #[test]
+    fn test_error_insufficient_funds() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        setup.client.deposit_funds(&setup.donor, &id, &99);
+
+        assert_eq!(setup.complete(id, 1), Err(Error::InsufficientFunds));
+    }
+
+    #[test]
+    fn test_error_milestones_incomplete() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+
+        assert_eq!(
+            setup.client.try_close_scholarship(&setup.donor, &id),
+            Err(Ok(Error::MilestonesIncomplete))
+        );
+    }
+
+    #[test]
+    fn test_dispute_paid() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(1_000);
+        let (id, arbitrator) = setup.disputed_scholarship(1000);
+
+        let dispute = setup.client.get_dispute(&id, &1).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Open);
+        assert_eq!(dispute.opened_by, setup.student);
+        assert_eq!(dispute.reason_hash, reason(&setup.env));
+        assert_eq!(dispute.amount, 400);
+        assert_eq!(dispute.opened_at, 1_000);
+        assert_eq!(dispute.deadline, 1_000 + DISPUTE_PERIOD);
+        assert_eq!(setup.client.get_scholarship(&id).frozen_amount, 400);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("disputed"), id).into_val(&setup.env),
+            (
+                1_u32,
+                setup.student.clone(),
+                reason(&setup.env),
+                dispute.deadline,
+            )
+                .into_val(&setup.env),
+        );
+
+        // The disputed milestone is frozen, the other one is not
+        assert_eq!(setup.complete(id, 1), Err(Error::MilestoneDisputed));
+        assert_eq!(setup.complete(id, 2), Ok(600));
+
+        assert_eq!(
+            setup.client.resolve_dispute(&arbitrator, &id, &1, &400),
+            DisputeStatus::Paid
+        );
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("resolved"), id).into_val(&setup.env),
+            (1_u32, DisputeStatus::Paid, 400_i128, 0_i128).into_val(&setup.env),
+        );
+
+        let scholarship = setup.client.get_scholarship(&id);
+        assert_eq!(scholarship.frozen_amount, 0);
+        assert_eq!(scholarship.released_amount, 1000);
+        assert_eq!(setup.client.get_balance(&id).total, 0);
+        assert_eq!(setup.token.balance(&setup.student), 1000);
+        assert!(
+            setup
+                .client
+                .get_milestones(&id)
+                .get(0)
+                .unwrap()
+                .is_completed
+        );
+        assert_eq!(
+            setup.client.get_dispute(&id, &1).unwrap().status,
+            DisputeStatus::Paid
+        );
+        assert_eq!(setup.complete(id, 1), Err(Error::MilestoneAlreadyCompleted));
+        assert_eq!(setup.client.close_scholarship(&setup.donor, &id), 0);
+    }
+
+    #[test]
+    fn test_dispute_refunded_and_split() {
+        let setup = Setup::new();
+        let (id, arbitrator) = setup.disputed_scholarship(1000);
+        setup
+            .client
+            .open_dispute(&setup.donor, &id, &2, &reason(&setup.env));
+        assert_eq!(setup.client.get_scholarship(&id).frozen_amount, 1000);
+
+        assert_eq!(
+            setup.client.resolve_dispute(&arbitrator, &id, &1, &0),
+            DisputeStatus::Refunded
+        );
+        // A fully refunded milestone is closed, not earned
+        let refunded = setup.client.get_milestones(&id).get(0).unwrap();
+        assert!(!refunded.is_completed);
+        assert!(refunded.is_expired);
+        assert_eq!(setup.client.get_completion_percentage(&id), 0);
+        assert_eq!(setup.client.get_milestone_completion(&id, &1), None);
+        assert_eq!(
+            setup
+                .client
+                .try_complete_milestone(&setup.verifier, &id, &1, &proof(&setup.env)),
+            Err(Ok(Error::MilestoneExpired))
+        );
+
+        assert_eq!(
+            setup.client.resolve_dispute(&arbitrator, &id, &2, &150),
+            DisputeStatus::Split
+        );
+
+        assert_eq!(setup.token.balance(&setup.student), 150);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000 - 150);
+        let scholarship = setup.client.get_scholarship(&id);
+        assert_eq!(scholarship.released_amount, 150);
+        assert_eq!(scholarship.frozen_amount, 0);
+        assert_eq!(setup.client.get_balance(&id).total, 0);
+        assert_eq!(setup.client.get_completion_percentage(&id), 50);
+
+        let disputes = setup.client.get_disputes(&id);
+        assert_eq!(disputes.len(), 2);
+        assert_eq!(disputes.get(0).unwrap().status, DisputeStatus::Refunded);
+        assert_eq!(disputes.get(1).unwrap().status, DisputeStatus::Split);
+        assert_eq!(disputes.get(1).unwrap().opened_by, setup.donor);
+    }
+
+    #[test]
+    fn test_dispute_expires() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(1_000);
+        let (id, arbitrator) = setup.disputed_scholarship(1000);
+        let deadline = setup.client.get_dispute(&id, &1).unwrap().deadline;
+
+        setup.env.ledger().set_timestamp(deadline);
+        assert_eq!(
+            setup.client.try_expire_dispute(&id, &1),
+            Err(Ok(Error::DeadlineNotReached))
+        );
+
+        setup.env.ledger().set_timestamp(deadline + 1);
+        setup.client.expire_dispute(&id, &1);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("disp_exp"), id).into_val(&setup.env),
+            1_u32.into_val(&setup.env),
+        );
+        assert_eq!(
+            setup.client.get_dispute(&id, &1).unwrap().status,
+            DisputeStatus::Expired
+        );
+        assert_eq!(setup.client.get_scholarship(&id).frozen_amount, 0);
+        assert_eq!(
+            setup.client.try_resolve_dispute(&arbitrator, &id, &1, &400),
+            Err(Ok(Error::DisputeNotOpen))
+        );
+
+        // The reward is unfrozen, but the milestone cannot be disputed again
+        assert_eq!(
+            setup
+                .client
+                .try_open_dispute(&setup.student, &id, &1, &reason(&setup.env)),
+            Err(Ok(Error::AlreadyDisputed))
+        );
+        assert_eq!(
+            setup.client.get_dispute(&id, &1).unwrap().status,
+            DisputeStatus::Expired
+        );
+        assert_eq!(setup.complete(id, 1), Ok(400));
+    }
+
+    #[test]
+    fn test_dispute_freezes_funds() {
+        let setup = Setup::new();
+        let (id, arbitrator) = setup.disputed_scholarship(800);
+
+        // Only 400 of the 800 deposited is free for milestone 2's 600
+        assert_eq!(setup.complete(id, 2), Err(Error::InsufficientFunds));
+
+        // Cancelling refunds only what is not frozen
+        assert_eq!(setup.client.cancel_scholarship(&setup.donor, &id), 400);
+        assert_eq!(setup.client.get_balance(&id).total, 400);
+        assert_eq!(setup.token.balance(&setup.client.address), 400);
+
+        // The arbitrator still rules on the frozen reward
+        setup.client.resolve_dispute(&arbitrator, &id, &1, &300);
+        assert_eq!(setup.token.balance(&setup.student), 300);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000 - 800 + 400 + 100);
+        assert_eq!(setup.token.balance(&setup.client.address), 0);
+    }
+
+    #[test]
+    fn test_expired_dispute_on_cancelled_scholarship_refunds_donor() {
+        let setup = Setup::new();
+        let (id, _) = setup.disputed_scholarship(1000);
+        assert_eq!(setup.client.cancel_scholarship(&setup.donor, &id), 600);
+
+        setup.env.ledger().set_timestamp(DISPUTE_PERIOD + 1);
+        setup.client.expire_dispute(&id, &1);
+        assert_eq!(setup.client.get_balance(&id).total, 0);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000);
+        assert_eq!(setup.token.balance(&setup.client.address), 0);
+    }
+
+    #[test]
+    fn test_error_arbitrator_not_set() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+
+        assert_eq!(
+            setup.client.try_get_arbitrator(),
+            Err(Ok(Error::ArbitratorNotSet))
+        );
+        assert_eq!(
+            setup
+                .client
+                .try_open_dispute(&setup.student, &id, &1, &reason(&setup.env)),
+            Err(Ok(Error::ArbitratorNotSet))
+        );
+    }
+
+    #[test]
+    fn test_error_unauthorized() {
+        let setup = Setup::new();
+        let (id, _) = setup.disputed_scholarship(1000);
+
+        // Only the arbitrator rules
+        assert_eq!(
+            setup.client.try_resolve_dispute(&setup.donor, &id, &1, &0),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            setup.client.try_set_arbitrator(&setup.donor, &setup.donor),
+            Err(Ok(Error::NotAdmin))
+        );
+
+        // Only the student or the donor opens disputes
+        assert_eq!(
+            setup
+                .client
+                .try_open_dispute(&setup.verifier, &id, &2, &reason(&setup.env)),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_error_already_disputed() {
+        let setup = Setup::new();
+        let (id, _) = setup.disputed_scholarship(1000);
+
+        assert_eq!(
+            setup
+                .client
+                .try_open_dispute(&setup.donor, &id, &1, &reason(&setup.env)),
+            Err(Ok(Error::AlreadyDisputed))
+        );
+    }
+
+    #[test]
+    fn test_error_dispute_not_found() {
+        let setup = Setup::new();
+        let (id, arbitrator) = setup.disputed_scholarship(1000);
+
+        assert_eq!(setup.client.get_dispute(&id, &2), None);
+        assert_eq!(
+            setup.client.try_resolve_dispute(&arbitrator, &id, &2, &0),
+            Err(Ok(Error::DisputeNotFound))
+        );
+        assert_eq!(
+            setup.client.try_expire_dispute(&id, &2),
+            Err(Ok(Error::DisputeNotFound))
+        );
+    }
+
+    #[test]
+    fn test_error_dispute_not_open() {
+        let setup = Setup::new();
+        let (id, arbitrator) = setup.disputed_scholarship(1000);
+        setup.client.resolve_dispute(&arbitrator, &id, &1, &0);
+
+        assert_eq!(
+            setup.client.try_resolve_dispute(&arbitrator, &id, &1, &0),
+            Err(Ok(Error::DisputeNotOpen))
+        );
+        assert_eq!(
+            setup.client.try_expire_dispute(&id, &1),
+            Err(Ok(Error::DisputeNotOpen))
+        );
+    }
+
+    #[test]
+    fn test_error_dispute_invalid_split() {
+        let setup = Setup::new();
+        let (id, arbitrator) = setup.disputed_scholarship(1000);
+
+        assert_eq!(
+            setup.client.try_resolve_dispute(&arbitrator, &id, &1, &401),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(
+            setup.client.try_resolve_dispute(&arbitrator, &id, &1, &-1),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+
+    #[test]
+    fn test_milestone_deadline_reclaim() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(1_000);
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            due_milestone(&setup.env, 1, 400, 2_000, ExpiryRule::Reclaim),
+            create_milestone(&setup.env, 2, 600),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &1000);
+
+        setup.env.ledger().set_timestamp(2_000);
+        assert_eq!(
+            setup.client.try_expire_milestone(&id, &1),
+            Err(Ok(Error::DeadlineNotReached))
+        );
+
+        setup.env.ledger().set_timestamp(2_001);
+        assert_eq!(setup.complete(id, 1), Err(Error::MilestoneExpired));
+        setup
+            .client
+            .set_arbitrator(&setup.admin, &Address::generate(&setup.env));
+        assert_eq!(
+            setup
+                .client
+                .try_open_dispute(&setup.student, &id, &1, &reason(&setup.env)),
+            Err(Ok(Error::MilestoneExpired))
+        );
+
+        assert_eq!(setup.client.expire_milestone(&id, &1), ExpiryRule::Reclaim);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("expired"), id).into_val(&setup.env),
+            (1_u32, ExpiryRule::Reclaim, 400_i128).into_val(&setup.env),
+        );
+        assert!(setup.client.get_milestones(&id).get(0).unwrap().is_expired);
+        assert_eq!(setup.client.get_balance(&id).total, 600);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000 - 600);
+        assert_eq!(
+            setup.client.try_expire_milestone(&id, &1),
+            Err(Ok(Error::MilestoneExpired))
+        );
+
+        // Milestones without a deadline are unaffected
+        assert_eq!(setup.complete(id, 2), Ok(600));
+        assert_eq!(setup.client.get_completion_percentage(&id), 50);
+        assert_eq!(setup.client.close_scholarship(&setup.donor, &id), 0);
+    }
+
+    #[test]
+    fn test_milestone_deadline_extend() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(1_000);
+        let id = setup.create_scholarship_until(
+            &vec![
+                &setup.env,
+                due_milestone(&setup.env, 1, 400, 2_000, ExpiryRule::Extend(200)),
+                due_milestone(&setup.env, 2, 600, 2_000, ExpiryRule::Extend(1_000)),
+            ],
+            Some(2_500),
+        );
+        setup.client.deposit_funds(&setup.donor, &id, &1000);
+
+        setup.env.ledger().set_timestamp(2_001);
+        assert_eq!(
+            setup.client.expire_milestone(&id, &1),
+            ExpiryRule::Extend(200)
+        );
+        assert_eq!(
+            setup.client.expire_milestone(&id, &2),
+            ExpiryRule::Extend(1_000)
+        );
+
+        // Extensions happen once and never go past the end date
+        let milestones = setup.client.get_milestones(&id);
+        assert_eq!(milestones.get(0).unwrap().deadline, Some(2_200));
+        assert_eq!(milestones.get(0).unwrap().on_expiry, ExpiryRule::Reclaim);
+        assert_eq!(milestones.get(1).unwrap().deadline, Some(2_500));
+        assert!(!milestones.get(0).unwrap().is_expired);
+
+        assert_eq!(setup.complete(id, 1), Ok(400));
+
+        setup.env.ledger().set_timestamp(2_501);
+        assert_eq!(setup.complete(id, 2), Err(Error::MilestoneExpired));
+        assert_eq!(setup.client.expire_milestone(&id, &2), ExpiryRule::Reclaim);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000 - 400);
+    }
+
+    #[test]
+    fn test_cancel_after_reclaim_refunds_net_stakes() {
+        let setup = Setup::new();
+        let alumni = Address::generate(&setup.env);
+        token::StellarAssetClient::new(&setup.env, &setup.token.address).mint(&alumni, &1_000);
+        setup.env.ledger().set_timestamp(1_000);
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            due_milestone(&setup.env, 1, 50, 2_000, ExpiryRule::Reclaim),
+            create_milestone(&setup.env, 2, 100),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+
+        // The donor gets half back when the first milestone expires
+        setup.env.ledger().set_timestamp(2_001);
+        setup.client.expire_milestone(&id, &1);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000 - 50);
+
+        // The donor now has 50 at stake and the alumni 100
+        setup.client.deposit_funds(&alumni, &id, &100);
+        assert_eq!(setup.client.cancel_scholarship(&setup.donor, &id), 150);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000);
+        assert_eq!(setup.token.balance(&alumni), 1_000);
+    }
+
+    #[test]
+    fn test_extend_falls_back_to_reclaim_after_end_date() {
+        let setup = Setup::new();
+        let mut milestone = create_milestone(&setup.env, 1, 400);
+        milestone.on_expiry = ExpiryRule::Extend(1_000);
+        let id = setup.create_scholarship_until(&vec![&setup.env, milestone], Some(2_000));
+        setup.client.deposit_funds(&setup.donor, &id, &400);
+
+        setup.env.ledger().set_timestamp(2_001);
+        assert_eq!(setup.client.expire_milestone(&id, &1), ExpiryRule::Reclaim);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000);
+    }
+
+    #[test]
+    fn test_milestone_deadline_forfeit_and_roll_over() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            due_milestone(&setup.env, 1, 100, 2_000, ExpiryRule::RollOver),
+            due_milestone(&setup.env, 2, 200, 3_000, ExpiryRule::Forfeit),
+            due_milestone(&setup.env, 3, 300, 4_000, ExpiryRule::RollOver),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &600);
+
+        setup.env.ledger().set_timestamp(2_001);
+        assert_eq!(setup.client.expire_milestone(&id, &1), ExpiryRule::RollOver);
+        assert_eq!(
+            setup
+                .client
+                .get_milestones(&id)
+                .get(1)
+                .unwrap()
+                .reward_amount,
+            300
+        );
+
+        // Forfeited rewards stay in escrow
+        setup.env.ledger().set_timestamp(3_001);
+        assert_eq!(setup.client.expire_milestone(&id, &2), ExpiryRule::Forfeit);
+        assert_eq!(setup.client.get_balance(&id).total, 600);
+
+        // Nothing left to roll over to, so the reward is refunded
+        setup.env.ledger().set_timestamp(4_001);
+        assert_eq!(setup.client.expire_milestone(&id, &3), ExpiryRule::Reclaim);
+        assert_eq!(setup.client.get_balance(&id).total, 300);
+
+        assert_eq!(setup.client.get_completion_percentage(&id), 0);
+        assert_eq!(setup.client.close_scholarship(&setup.donor, &id), 300);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000);
+    }
+
+    #[test]
+    fn test_disputed_milestone_does_not_expire() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            due_milestone(&setup.env, 1, 400, 2_000, ExpiryRule::Reclaim),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &400);
+        let arbitrator = Address::generate(&setup.env);
+        setup.client.set_arbitrator(&setup.admin, &arbitrator);
+        setup
+            .client
+            .open_dispute(&setup.student, &id, &1, &reason(&setup.env));
+
+        setup.env.ledger().set_timestamp(2_001);
+        assert_eq!(
+            setup.client.try_expire_milestone(&id, &1),
+            Err(Ok(Error::MilestoneDisputed))
+        );
+        setup.client.resolve_dispute(&arbitrator, &id, &1, &400);
+        assert_eq!(setup.token.balance(&setup.student), 400);
+    }
+
+    #[test]
+    fn test_error_invalid_deadline() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(1_000);
+        let create = |milestone: Milestone, end_date: Option<u64>| {
+            setup.client.try_create_scholarship(
+                &setup.donor,
+                &setup.student,
+                &400,
+                &setup.token.address,
+                &String::from_str(&setup.env, "USDC"),
+                &vec![&setup.env, milestone],
+                &vec![&setup.env, setup.verifier.clone()],
+                &end_date,
+            )
+        };
+
+        // End date in the past
+        assert_eq!(
+            create(create_milestone(&setup.env, 1, 400), Some(1_000)),
+            Err(Ok(Error::InvalidDeadline))
+        );
+        // Deadline in the past
+        assert_eq!(
+            create(
+                due_milestone(&setup.env, 1, 400, 1_000, ExpiryRule::Reclaim),
+                None
+            ),
+            Err(Ok(Error::InvalidDeadline))
+        );
+        // Deadline after the end date
+        assert_eq!(
+            create(
+                due_milestone(&setup.env, 1, 400, 3_000, ExpiryRule::Reclaim),
+                Some(2_000)
+            ),
+            Err(Ok(Error::InvalidDeadline))
+        );
+
+        let mut expired = create_milestone(&setup.env, 1, 400);
+        expired.is_expired = true;
+        assert_eq!(create(expired, None), Err(Ok(Error::MilestoneExpired)));
+    }
+
+    /// Scholarship with one (400) milestone, funded, and three verifiers
+    /// with `quorum`. Returns the scholarship id and the verifiers.
+    fn quorum_scholarship(setup: &Setup, quorum: u32) -> (u64, [Address; 3]) {
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 400)]);
+        setup.client.deposit_funds(&setup.donor, &id, &400);
+        let verifiers = [
+            setup.verifier.clone(),
+            Address::generate(&setup.env),
+            Address::generate(&setup.env),
+        ];
+        setup.client.add_verifier(&setup.donor, &id, &verifiers[1]);
+        setup.client.add_verifier(&setup.donor, &id, &verifiers[2]);
+        setup.client.set_quorum(&setup.donor, &id, &quorum);
+        (id, verifiers)
+    }
+
+    #[test]
+    fn test_quorum_approvals() {
+        let setup = Setup::new();
+        let (id, [v1, v2, _]) = quorum_scholarship(&setup, 2);
+        assert_eq!(setup.client.get_scholarship(&id).quorum, 2);
+
+        assert_eq!(
+            setup
+                .client
+                .approve_milestone(&v1, &id, &1, &proof(&setup.env)),
+            1
+        );
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("approved"), id).into_val(&setup.env),
+            (1_u32, v1.clone(), 1_u32).into_val(&setup.env),
+        );
+        assert_eq!(
+            setup.client.get_approvals(&id, &1),
+            vec![&setup.env, v1.clone()]
+        );
+        assert!(
+            !setup
+                .client
+                .get_milestones(&id)
+                .get(0)
+                .unwrap()
+                .is_completed
+        );
+        assert_eq!(setup.token.balance(&setup.student), 0);
+
+        // The second approval reaches the quorum and releases the reward
+        assert_eq!(
+            setup
+                .client
+                .approve_milestone(&v2, &id, &1, &proof(&setup.env)),
+            2
+        );
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("completed"), id).into_val(&setup.env),
+            (1_u32, v2.clone(), setup.student.clone(), 400_i128).into_val(&setup.env),
+        );
+        assert!(
+            setup
+                .client
+                .get_milestones(&id)
+                .get(0)
+                .unwrap()
+                .is_completed
+        );
+        assert_eq!(setup.token.balance(&setup.student), 400);
+        assert_eq!(
+            setup
+                .client
+                .get_milestone_completion(&id, &1)
+                .map(|completion| completion.completed_by),
+            Some(Completer::Verifier(v2.clone()))
+        );
+        assert_eq!(
+            setup.client.try_withdraw_approval(&v1, &id, &1),
+            Err(Ok(Error::MilestoneAlreadyCompleted))
+        );
+    }
+
+    #[test]
+    fn test_withdraw_approval() {
+        let setup = Setup::new();
+        let (id, [v1, v2, v3]) = quorum_scholarship(&setup, 3);
+
+        setup
+            .client
+            .approve_milestone(&v1, &id, &1, &proof(&setup.env));
+        setup
+            .client
+            .approve_milestone(&v2, &id, &1, &proof(&setup.env));
+        assert_eq!(setup.client.withdraw_approval(&v2, &id, &1), 1);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("unapprove"), id).into_val(&setup.env),
+            (1_u32, v2.clone()).into_val(&setup.env),
+        );
+        assert_eq!(
+            setup
+                .client
+                .approve_milestone(&v3, &id, &1, &proof(&setup.env)),
+            2
+        );
+        assert_eq!(setup.token.balance(&setup.student), 0);
+
+        // complete_milestone counts as the caller's approval on top of the others
+        assert_eq!(
+            setup.client.try_complete_milestone(
+                &v1,
+                &id,
+                &1,
+                &String::from_str(&setup.env, "exam_score_85")
+            ),
+            Err(Ok(Error::QuorumNotReached))
+        );
+        assert_eq!(
+            setup.client.complete_milestone(
+                &v2,
+                &id,
+                &1,
+                &String::from_str(&setup.env, "exam_score_85")
+            ),
+            400
+        );
+    }
+
+    #[test]
+    fn test_quorum_before_progress() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            Milestone {
+                required_progress: 50,
+                ..create_milestone(&setup.env, 1, 400)
+            },
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &400);
+        let v2 = Address::generate(&setup.env);
+        setup.client.add_verifier(&setup.donor, &id, &v2);
+        setup.client.set_quorum(&setup.donor, &id, &2);
+
+        // The quorum is reached before the student's progress: both approvals stay
+        setup
+            .client
+            .approve_milestone(&setup.verifier, &id, &1, &proof(&setup.env));
+        assert_eq!(
+            setup
+                .client
+                .approve_milestone(&v2, &id, &1, &proof(&setup.env)),
+            2
+        );
+        assert_eq!(
+            setup.client.get_approvals(&id, &1),
+            vec![&setup.env, setup.verifier.clone(), v2.clone()]
+        );
+        assert_eq!(setup.token.balance(&setup.student), 0);
+        assert_eq!(setup.complete(id, 1), Err(Error::ProgressNotReached));
+
+        setup.progress.update_progress(
+            &setup.reporter,
+            &setup.student,
+            &COURSE,
+            &25,
+            &evidence(&setup.env),
+        );
+        assert_eq!(setup.complete(id, 1), Ok(400));
+        assert_eq!(setup.token.balance(&setup.student), 400);
+    }
+
+    #[test]
+    fn test_removed_verifier_approval_does_not_count() {
+        let setup = Setup::new();
+        let (id, [v1, v2, v3]) = quorum_scholarship(&setup, 2);
+
+        setup
+            .client
+            .approve_milestone(&v1, &id, &1, &proof(&setup.env));
+        setup.client.remove_verifier(&setup.donor, &id, &v1);
+
+        assert_eq!(
+            setup
+                .client
+                .approve_milestone(&v2, &id, &1, &proof(&setup.env)),
+            1
+        );
+        assert_eq!(setup.token.balance(&setup.student), 0);
+        assert_eq!(
+            setup
+                .client
+                .approve_milestone(&v3, &id, &1, &proof(&setup.env)),
+            2
+        );
+        assert_eq!(setup.token.balance(&setup.student), 400);
+    }
+
+    #[test]
+    fn test_error_invalid_quorum() {
+        let setup = Setup::new();
+        let (id, [v1, _, _]) = quorum_scholarship(&setup, 3);
+
+        assert_eq!(
+            setup.client.try_set_quorum(&setup.donor, &id, &0),
+            Err(Ok(Error::InvalidQuorum))
+        );
+        assert_eq!(
+            setup.client.try_set_quorum(&setup.donor, &id, &4),
+            Err(Ok(Error::InvalidQuorum))
+        );
+        assert_eq!(
+            setup.client.try_set_quorum(&v1, &id, &1),
+            Err(Ok(Error::NotDonor))
+        );
+        // Removing a verifier would leave fewer than the quorum
+        assert_eq!(
+            setup.client.try_remove_verifier(&setup.donor, &id, &v1),
+            Err(Ok(Error::InvalidQuorum))
+        );
+    }
+
+    #[test]
+    fn test_error_already_approved() {
+        let setup = Setup::new();
+        let (id, [v1, _, _]) = quorum_scholarship(&setup, 2);
+        setup
+            .client
+            .approve_milestone(&v1, &id, &1, &proof(&setup.env));
+
+        assert_eq!(
+            setup
+                .client
+                .try_approve_milestone(&v1, &id, &1, &proof(&setup.env)),
+            Err(Ok(Error::AlreadyApproved))
+        );
+    }
+
+    #[test]
+    fn test_error_approval_not_found() {
+        let setup = Setup::new();
+        let (id, [v1, _, _]) = quorum_scholarship(&setup, 2);
+
+        assert_eq!(
+            setup.client.try_withdraw_approval(&v1, &id, &1),
+            Err(Ok(Error::ApprovalNotFound))
+        );
+
+        setup
+            .client
+            .approve_milestone(&v1, &id, &1, &proof(&setup.env));
+        setup.client.cancel_scholarship(&setup.donor, &id);
+        assert_eq!(
+            setup.client.try_withdraw_approval(&v1, &id, &1),
+            Err(Ok(Error::ScholarshipNotActive))
+        );
+    }
+
+    #[test]
+    fn test_error_quorum_not_reached() {
+        let setup = Setup::new();
+        let (id, _) = quorum_scholarship(&setup, 2);
+
+        assert_eq!(setup.complete(id, 1), Err(Error::QuorumNotReached));
+    }
+
+    #[test]
+    fn test_pooled_contributions() {
+        let setup = Setup::new();
+        let alumni = Address::generate(&setup.env);
+        let ngo = Address::generate(&setup.env);
+        let sac = token::StellarAssetClient::new(&setup.env, &setup.token.address);
+        sac.mint(&alumni, &1_000);
+        sac.mint(&ngo, &1_000);
+
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            create_milestone(&setup.env, 1, 400),
+            create_milestone(&setup.env, 2, 600),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &500);
+        setup.client.deposit_funds(&alumni, &id, &200);
+        setup.client.deposit_funds(&ngo, &id, &200);
+        setup.client.deposit_funds(&alumni, &id, &100);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("deposit"), id).into_val(&setup.env),
+            (alumni.clone(), 100_i128, 1_000_i128).into_val(&setup.env),
+        );
+
+        let contributors = setup.client.get_contributors(&id);
+        assert_eq!(
+            contributors,
+            vec![
+                &setup.env,
+                Contribution {
+                    contributor: setup.donor.clone(),
+                    amount: 500,
+                    refunded: 0,
+                },
+                Contribution {
+                    contributor: alumni.clone(),
+                    amount: 300,
+                    refunded: 0,
+                },
+                Contribution {
+                    contributor: ngo.clone(),
+                    amount: 200,
+                    refunded: 0,
+                },
+            ]
+        );
+
+        // 600 left after the first payout goes back 50/30/20
+        assert_eq!(setup.complete(id, 1), Ok(400));
+        assert_eq!(setup.client.cancel_scholarship(&setup.donor, &id), 600);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000 - 500 + 300);
+        assert_eq!(setup.token.balance(&alumni), 1_000 - 300 + 180);
+        assert_eq!(setup.token.balance(&ngo), 1_000 - 200 + 120);
+        assert_eq!(setup.token.balance(&setup.client.address), 0);
+
+        let refunded: std::vec::Vec<i128> = setup
+            .client
+            .get_contributors(&id)
+            .iter()
+            .map(|share| share.refunded)
+            .collect();
+        assert_eq!(refunded, std::vec![300, 180, 120]);
+    }
+
+    #[test]
+    fn test_pro_rata_remainder_goes_to_first_contributor() {
+        let setup = Setup::new();
+        let alumni = Address::generate(&setup.env);
+        token::StellarAssetClient::new(&setup.env, &setup.token.address).mint(&alumni, &1_000);
+
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 300)]);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+        setup.client.deposit_funds(&alumni, &id, &200);
+
+        // 100 split 1:2 is 33 and 66, the leftover unit goes to the donor
+        setup.client.set_arbitrator(&setup.admin, &setup.admin);
+        setup
+            .client
+            .open_dispute(&setup.student, &id, &1, &reason(&setup.env));
+        setup.client.resolve_dispute(&setup.admin, &id, &1, &200);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000 - 100 + 34);
+        assert_eq!(setup.token.balance(&alumni), 1_000 - 200 + 66);
+    }
+
+    #[test]
+    fn test_pro_rata_remainder_is_capped_at_stake() {
+        let setup = Setup::new();
+        let alumni = Address::generate(&setup.env);
+        let sponsor = Address::generate(&setup.env);
+        let asset = token::StellarAssetClient::new(&setup.env, &setup.token.address);
+        asset.mint(&alumni, &1_000);
+        asset.mint(&sponsor, &1_000);
+        setup.env.ledger().set_timestamp(1_000);
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            due_milestone(&setup.env, 1, 150, 2_000, ExpiryRule::Reclaim),
+            create_milestone(&setup.env, 2, 51),
+        ]);
+        setup.client.set_min_contribution(&setup.donor, &id, &1);
+        setup.client.deposit_funds(&setup.donor, &id, &1);
+        setup.client.deposit_funds(&alumni, &id, &100);
+        setup.client.deposit_funds(&sponsor, &id, &100);
+
+        // 150 split 1:100:100 is 0, 74 and 74; the donor gets one leftover unit, up to
+        // its stake, and the alumni the other
+        setup.env.ledger().set_timestamp(2_001);
+        setup.client.expire_milestone(&id, &1);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000);
+        assert_eq!(setup.token.balance(&alumni), 1_000 - 100 + 75);
+        assert_eq!(setup.token.balance(&sponsor), 1_000 - 100 + 74);
+
+        // A second refund still goes through and returns every stake in full
+        assert_eq!(setup.client.cancel_scholarship(&setup.donor, &id), 51);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000);
+        assert_eq!(setup.token.balance(&alumni), 1_000);
+        assert_eq!(setup.token.balance(&sponsor), 1_000);
+    }
+
+    #[test]
+    fn test_error_below_min_contribution() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        setup.client.set_min_contribution(&setup.donor, &id, &50);
+        assert_eq!(setup.client.get_scholarship(&id).min_contribution, 50);
+
+        assert_eq!(
+            setup.client.try_deposit_funds(&setup.donor, &id, &49),
+            Err(Ok(Error::BelowMinContribution))
+        );
+        setup.client.deposit_funds(&setup.donor, &id, &50);
+        assert_eq!(
+            setup.client.try_set_min_contribution(&setup.donor, &id, &0),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+
+    #[test]
+    fn test_default_min_contribution() {
+        let setup = Setup::new();
+        let id =
+            setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 1_000)]);
+        assert_eq!(setup.client.get_scholarship(&id).min_contribution, 20);
+        assert_eq!(
+            setup.client.try_deposit_funds(&setup.donor, &id, &19),
+            Err(Ok(Error::BelowMinContribution))
+        );
+
+        let small =
+            setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 10)]);
+        assert_eq!(setup.client.get_scholarship(&small).min_contribution, 1);
+    }
+
+    #[test]
+    fn test_error_too_many_contributors() {
+        let setup = Setup::new();
+        setup.env.budget().reset_unlimited();
+        let sac = token::StellarAssetClient::new(&setup.env, &setup.token.address);
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+        setup.client.set_min_contribution(&setup.donor, &id, &1);
+
+        setup.client.deposit_funds(&setup.donor, &id, &1);
+        for _ in 1..MAX_CONTRIBUTORS {
+            let contributor = Address::generate(&setup.env);
+            sac.mint(&contributor, &1);
+            setup.client.deposit_funds(&contributor, &id, &1);
+        }
+
+        let contributor = Address::generate(&setup.env);
+        sac.mint(&contributor, &1);
+        assert_eq!(
+            setup.client.try_deposit_funds(&contributor, &id, &1),
+            Err(Ok(Error::TooManyContributors))
+        );
+        // Existing contributors can still add to their share
+        setup.client.deposit_funds(&setup.donor, &id, &10);
+
+        // A sponsor who has no share yet doesn't match them
+        let (pool_id, sponsor) = matching_pool(&setup, 1_000, 10_000, 1_000);
+        setup.client.attach_matching_pool(&sponsor, &pool_id, &id);
+        setup.client.deposit_funds(&setup.donor, &id, &10);
+        assert_eq!(setup.client.get_balance(&id).total, 70);
+        assert_eq!(setup.client.get_pool_matched(&pool_id, &id), 0);
+        assert_eq!(setup.client.get_contributors(&id).len(), MAX_CONTRIBUTORS);
+    }
+
+    /// Fund a matching pool of `amount` at `ratio_bps` with `cap`, ending at 10_000
+    fn matching_pool(setup: &Setup, amount: i128, ratio_bps: u32, cap: i128) -> (u32, Address) {
+        let sponsor = Address::generate(&setup.env);
+        token::StellarAssetClient::new(&setup.env, &setup.token.address).mint(&sponsor, &amount);
+        let pool_id = setup.client.create_matching_pool(
+            &sponsor,
+            &setup.token.address,
+            &amount,
+            &ratio_bps,
+            &cap,
+            &10_000,
+        );
+        (pool_id, sponsor)
+    }
+
+    #[test]
+    fn test_matching_pool() {
+        let setup = Setup::new();
+        let (pool_id, sponsor) = matching_pool(&setup, 800, 10_000, 500);
+        let milestones = vec![&setup.env, create_milestone(&setup.env, 1, 2_000)];
+        let first = setup.create_scholarship(&milestones);
+        let second = setup.create_scholarship(&milestones);
+        setup
+            .client
+            .attach_matching_pool(&sponsor, &pool_id, &first);
+        setup
+            .client
+            .attach_matching_pool(&sponsor, &pool_id, &second);
+        assert_eq!(
+            setup.client.get_scholarship(&first).matching_pool,
+            Some(pool_id)
+        );
+
+        // Matched 1:1
+        setup.client.deposit_funds(&setup.donor, &first, &300);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("matched"), first).into_val(&setup.env),
+            (pool_id, 300_i128, 600_i128).into_val(&setup.env),
+        );
+        assert_eq!(setup.client.get_balance(&first).total, 600);
+
+        // Up to the per-scholarship cap
+        setup.client.deposit_funds(&setup.donor, &first, &300);
+        assert_eq!(setup.client.get_balance(&first).total, 1_100);
+        assert_eq!(setup.client.get_pool_matched(&pool_id, &first), 500);
+
+        // Up to what is left in the pool
+        setup.client.deposit_funds(&setup.donor, &second, &400);
+        assert_eq!(setup.client.get_balance(&second).total, 700);
+        assert_eq!(setup.client.get_pool_matched(&pool_id, &second), 300);
+
+        let pool = setup.client.get_matching_pool(&pool_id);
+        assert_eq!(pool.remaining, 0);
+        assert_eq!(pool.matched, 800);
+
+        // Matched funds are the sponsor's share of the scholarship
+        let contributors = setup.client.get_contributors(&first);
+        assert_eq!(contributors.get(1).unwrap().contributor, sponsor);
+        assert_eq!(contributors.get(1).unwrap().amount, 500);
+        assert_eq!(setup.client.cancel_scholarship(&setup.donor, &first), 1_100);
+        assert_eq!(setup.token.balance(&sponsor), 500);
+    }
+
+    #[test]
+    fn test_matching_pool_ends_and_is_reclaimed() {
+        let setup = Setup::new();
+        let (pool_id, sponsor) = matching_pool(&setup, 1_000, 5_000, 1_000);
+        let id =
+            setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 2_000)]);
+        setup.client.attach_matching_pool(&sponsor, &pool_id, &id);
+
+        // 1:2
+        setup.client.deposit_funds(&setup.donor, &id, &300);
+        assert_eq!(setup.client.get_balance(&id).total, 450);
+
+        assert_eq!(
+            setup.client.try_reclaim_matching_pool(&sponsor, &pool_id),
+            Err(Ok(Error::DeadlineNotReached))
+        );
+
+        // Deposits after the end date are not matched
+        setup.env.ledger().set_timestamp(10_001);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+        assert_eq!(setup.client.get_balance(&id).total, 550);
+
+        assert_eq!(setup.client.reclaim_matching_pool(&sponsor, &pool_id), 850);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("pool_recl"), pool_id).into_val(&setup.env),
+            (sponsor.clone(), 850_i128).into_val(&setup.env),
+        );
+        assert_eq!(setup.token.balance(&sponsor), 850);
+        assert_eq!(setup.client.get_matching_pool(&pool_id).remaining, 0);
+        assert_eq!(setup.client.reclaim_matching_pool(&sponsor, &pool_id), 0);
+    }
+
+    #[test]
+    fn test_sponsor_deposits_are_not_matched() {
+        let setup = Setup::new();
+        let (pool_id, sponsor) = matching_pool(&setup, 1_000, 10_000, 1_000);
+        token::StellarAssetClient::new(&setup.env, &setup.token.address).mint(&sponsor, &100);
+        let id =
+            setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 2_000)]);
+        setup.client.attach_matching_pool(&sponsor, &pool_id, &id);
+
+        setup.client.deposit_funds(&sponsor, &id, &100);
+        assert_eq!(setup.client.get_balance(&id).total, 100);
+        assert_eq!(setup.client.get_matching_pool(&pool_id).remaining, 1_000);
+    }
+
+    #[test]
+    fn test_matching_pool_needs_the_donor() {
+        let setup = Setup::new();
+        let (pool_id, sponsor) = matching_pool(&setup, 1, 10_000, 1);
+        let id =
+            setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 2_000)]);
+
+        // The donor co-signs the attach
+        setup.client.attach_matching_pool(&sponsor, &pool_id, &id);
+        let signers: std::vec::Vec<Address> = setup
+            .env
+            .auths()
+            .into_iter()
+            .map(|(signer, _)| signer)
+            .collect();
+        assert_eq!(signers, std::vec![sponsor.clone(), setup.donor.clone()]);
+
+        // and can swap the pool for another one
+        assert_eq!(
+            setup.client.try_detach_matching_pool(&sponsor, &id),
+            Err(Ok(Error::NotDonor))
+        );
+        setup.client.detach_matching_pool(&setup.donor, &id);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("pool_det"), pool_id).into_val(&setup.env),
+            id.into_val(&setup.env),
+        );
+        assert_eq!(setup.client.get_scholarship(&id).matching_pool, None);
+        assert_eq!(
+            setup.client.try_detach_matching_pool(&setup.donor, &id),
+            Err(Ok(Error::MatchingPoolNotFound))
+        );
+
+        let (other_pool, other_sponsor) = matching_pool(&setup, 1_000, 10_000, 1_000);
+        setup
+            .client
+            .attach_matching_pool(&other_sponsor, &other_pool, &id);
+        setup.client.deposit_funds(&setup.donor, &id, &300);
+        assert_eq!(setup.client.get_balance(&id).total, 600);
+    }
+
+    #[test]
+    fn test_error_matching_pool() {
+        let setup = Setup::new();
+        let (pool_id, sponsor) = matching_pool(&setup, 1_000, 10_000, 1_000);
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+
+        assert_eq!(
+            setup.client.try_get_matching_pool(&99),
+            Err(Ok(Error::MatchingPoolNotFound))
+        );
+        assert_eq!(
+            setup
+                .client
+                .try_attach_matching_pool(&setup.donor, &pool_id, &id),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            setup
+                .client
+                .try_reclaim_matching_pool(&setup.donor, &pool_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            setup.client.try_create_matching_pool(
+                &sponsor,
+                &setup.token.address,
+                &100,
+                &10_000,
+                &100,
+                &0,
+            ),
+            Err(Ok(Error::InvalidDeadline))
+        );
+
+        // The pool pays in a different token
+        let (other_token, other_admin) = create_token(&setup.env, &sponsor);
+        other_admin.mint(&sponsor, &100);
+        let other_pool = setup.client.create_matching_pool(
+            &sponsor,
+            &other_token.address,
+            &100,
+            &10_000,
+            &100,
+            &10_000,
+        );
+        assert_eq!(
+            setup
+                .client
+                .try_attach_matching_pool(&sponsor, &other_pool, &id),
+            Err(Ok(Error::TokenMismatch))
+        );
+
+        setup.client.attach_matching_pool(&sponsor, &pool_id, &id);
+        assert_eq!(
+            setup
+                .client
+                .try_attach_matching_pool(&sponsor, &pool_id, &id),
+            Err(Ok(Error::MatchingPoolAttached))
+        );
+    }
+
+    /// Funded 1000-token stream from 1_000 to 2_000, created at 1_000
+    fn funded_stream(setup: &Setup, cliff: Option<u64>) -> u64 {
+        setup.env.ledger().set_timestamp(1_000);
+        let id = setup.client.create_stream(
+            &setup.donor,
+            &setup.student,
+            &1_000,
+            &setup.token.address,
+            &String::from_str(&setup.env, "USDC"),
+            &1_000,
+            &2_000,
+            &cliff,
+        );
+        setup.client.deposit_funds(&setup.donor, &id, &1_000);
+        id
+    }
+
+    #[test]
+    fn test_stream_vests_linearly() {
+        let setup = Setup::new();
+        let id = funded_stream(&setup, None);
+        let stream = setup.client.get_stream(&id);
+        assert_eq!(
+            (stream.start, stream.end, stream.cliff),
+            (1_000, 2_000, 1_000)
+        );
+        assert!(setup.client.get_milestones(&id).is_empty());
+
+        setup.env.ledger().set_timestamp(1_250);
+        assert_eq!(setup.client.get_vested(&id), 250);
+        assert_eq!(setup.client.withdraw_vested(&setup.student, &id), 250);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("vested"), id).into_val(&setup.env),
+            (setup.student.clone(), 250_i128, 250_i128).into_val(&setup.env),
+        );
+        assert_eq!(
+            setup.client.try_withdraw_vested(&setup.student, &id),
+            Err(Ok(Error::NothingToWithdraw))
+        );
+        assert_eq!(setup.client.get_completion_percentage(&id), 25);
+
+        // Fully vested after the end
+        setup.env.ledger().set_timestamp(3_000);
+        assert_eq!(setup.client.withdraw_vested(&setup.student, &id), 750);
+        assert_eq!(setup.token.balance(&setup.student), 1_000);
+        assert_eq!(setup.client.get_scholarship(&id).released_amount, 1_000);
+        assert_eq!(setup.client.get_completion_percentage(&id), 100);
+        assert_eq!(setup.client.close_scholarship(&setup.donor, &id), 0);
+    }
+
+    #[test]
+    fn test_stream_cliff() {
+        let setup = Setup::new();
+        let id = funded_stream(&setup, Some(1_500));
+
+        setup.env.ledger().set_timestamp(1_499);
+        assert_eq!(setup.client.get_vested(&id), 0);
+        assert_eq!(
+            setup.client.try_withdraw_vested(&setup.student, &id),
+            Err(Ok(Error::NothingToWithdraw))
+        );
+
+        // Everything vested up to the cliff unlocks at once
+        setup.env.ledger().set_timestamp(1_500);
+        assert_eq!(setup.client.withdraw_vested(&setup.student, &id), 500);
+    }
+
+    #[test]
+    fn test_stream_pause() {
+        let setup = Setup::new();
+        let id = funded_stream(&setup, None);
+
+        setup.env.ledger().set_timestamp(1_200);
+        setup.client.pause_stream(&setup.donor, &id);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("paused"), id).into_val(&setup.env),
+            1_200_u64.into_val(&setup.env),
+        );
+
+        // Nothing vests while paused, but what vested before can be withdrawn
+        setup.env.ledger().set_timestamp(1_600);
+        assert_eq!(setup.client.get_vested(&id), 200);
+        assert_eq!(setup.client.withdraw_vested(&setup.student, &id), 200);
+
+        setup.client.resume_stream(&setup.donor, &id);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("resumed"), id).into_val(&setup.env),
+            400_u64.into_val(&setup.env),
+        );
+
+        // The schedule moved back by the 400 seconds spent paused
+        setup.env.ledger().set_timestamp(1_700);
+        assert_eq!(setup.client.get_vested(&id), 300);
+        setup.env.ledger().set_timestamp(2_399);
+        assert_eq!(setup.client.get_vested(&id), 999);
+        setup.env.ledger().set_timestamp(2_400);
+        assert_eq!(setup.client.get_vested(&id), 1_000);
+    }
+
+    #[test]
+    fn test_stream_pause_before_start() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(50);
+        let id = setup.client.create_stream(
+            &setup.donor,
+            &setup.student,
+            &1_000,
+            &setup.token.address,
+            &String::from_str(&setup.env, "USDC"),
+            &1_000,
+            &2_000,
+            &None,
+        );
+        setup.client.deposit_funds(&setup.donor, &id, &1_000);
+
+        // A pause that ends before the start does not move the schedule
+        setup.env.ledger().set_timestamp(100);
+        setup.client.pause_stream(&setup.donor, &id);
+        setup.env.ledger().set_timestamp(900);
+        setup.client.resume_stream(&setup.donor, &id);
+        assert_eq!(setup.client.get_stream(&id).paused_for, 0);
+
+        // One across the start only counts from the start
+        setup.client.pause_stream(&setup.donor, &id);
+        setup.env.ledger().set_timestamp(1_100);
+        setup.client.resume_stream(&setup.donor, &id);
+        assert_eq!(setup.client.get_stream(&id).paused_for, 100);
+
+        setup.env.ledger().set_timestamp(2_000);
+        assert_eq!(setup.client.get_vested(&id), 900);
+        setup.env.ledger().set_timestamp(2_100);
+        assert_eq!(setup.client.get_vested(&id), 1_000);
+    }
+
+    #[test]
+    fn test_stream_cancel_pays_vested() {
+        let setup = Setup::new();
+        let id = funded_stream(&setup, None);
+
+        setup.env.ledger().set_timestamp(1_300);
+        assert_eq!(setup.client.cancel_scholarship(&setup.donor, &id), 700);
+        assert_eq!(setup.token.balance(&setup.student), 300);
+        assert_eq!(setup.token.balance(&setup.donor), 10_000 - 300);
+        assert_eq!(
+            setup.client.try_withdraw_vested(&setup.student, &id),
+            Err(Ok(Error::ScholarshipNotActive))
+        );
+    }
+
+    #[test]
+    fn test_stream_pays_no_more_than_deposited() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(1_000);
+        let id = setup.client.create_stream(
+            &setup.donor,
+            &setup.student,
+            &1_000,
+            &setup.token.address,
+            &String::from_str(&setup.env, "USDC"),
+            &1_000,
+            &2_000,
+            &None,
+        );
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+
+        setup.env.ledger().set_timestamp(1_500);
+        assert_eq!(setup.client.withdraw_vested(&setup.student, &id), 100);
+        setup.client.deposit_funds(&setup.donor, &id, &900);
+        assert_eq!(setup.client.withdraw_vested(&setup.student, &id), 400);
+    }
+
+    #[test]
+    fn test_error_invalid_schedule() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(1_000);
+        let create = |start: u64, end: u64, cliff: Option<u64>| {
+            setup.client.try_create_stream(
+                &setup.donor,
+                &setup.student,
+                &1_000,
+                &setup.token.address,
+                &String::from_str(&setup.env, "USDC"),
+                &start,
+                &end,
+                &cliff,
+            )
+        };
+
+        assert_eq!(create(2_000, 2_000, None), Err(Ok(Error::InvalidSchedule)));
+        assert_eq!(create(500, 1_000, None), Err(Ok(Error::InvalidSchedule)));
+        assert_eq!(
+            create(1_000, 2_000, Some(999)),
+            Err(Ok(Error::InvalidSchedule))
+        );
+        assert_eq!(
+            create(1_000, 2_000, Some(2_001)),
+            Err(Ok(Error::InvalidSchedule))
+        );
+        // A stream can start in the past as long as it has not ended
+        assert!(create(500, 2_000, None).is_ok());
+    }
+
+    #[test]
+    fn test_error_stream() {
+        let setup = Setup::new();
+        let id = funded_stream(&setup, None);
+        let milestone_id =
+            setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 100)]);
+
+        assert_eq!(
+            setup
+                .client
+                .try_withdraw_vested(&setup.student, &milestone_id),
+            Err(Ok(Error::ScheduleNotFound))
+        );
+        assert_eq!(
+            setup.client.try_withdraw_vested(&setup.donor, &id),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            setup.client.try_pause_stream(&setup.student, &id),
+            Err(Ok(Error::NotDonor))
+        );
+        assert_eq!(
+            setup.client.try_resume_stream(&setup.donor, &id),
+            Err(Ok(Error::StreamNotPaused))
+        );
+        setup.client.pause_stream(&setup.donor, &id);
+        assert_eq!(
+            setup.client.try_pause_stream(&setup.donor, &id),
+            Err(Ok(Error::StreamPaused))
+        );
+        assert_eq!(
+            setup.client.try_close_scholarship(&setup.donor, &id),
+            Err(Ok(Error::DeadlineNotReached))
+        );
+    }
+
+    /// Scholarship of 1000: milestone 1 worth 400 and a stipend of 6 x 100 every
+    /// 1_000 seconds from 1_000
+    fn hybrid_scholarship(setup: &Setup) -> u64 {
+        setup.env.ledger().set_timestamp(1_000);
+        let id = setup.client.create_scholarship(
+            &setup.donor,
+            &setup.student,
+            &1_000,
+            &setup.token.address,
+            &String::from_str(&setup.env, "USDC"),
+            &vec![&setup.env, create_milestone(&setup.env, 1, 400)],
+            &vec![&setup.env, setup.verifier.clone()],
+            &None,
+        );
+        setup
+            .client
+            .add_stipend(&setup.donor, &id, &100, &1_000, &6, &1_000);
+        id
+    }
+
+    #[test]
+    fn test_hybrid_scholarship() {
+        let setup = Setup::new();
+        let id = hybrid_scholarship(&setup);
+        assert_eq!(
+            setup.client.get_stipend(&id),
+            Stipend {
+                amount: 100,
+                period: 1_000,
+                periods: 6,
+                start: 1_000,
+                paid_periods: 0,
+            }
+        );
+
+        setup.client.deposit_funds(&setup.donor, &id, &1_000);
+        assert_eq!(
+            setup.client.get_balance(&id),
+            PoolBalance {
+                milestones: 400,
+                stipend: 600,
+                frozen: 0,
+                total: 1_000,
+            }
+        );
+
+        // The first period is due at the start
+        assert_eq!(setup.client.claim_stipend(&setup.student, &id), 100);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("stipend"), id).into_val(&setup.env),
+            (setup.student.clone(), 100_i128, 1_u32).into_val(&setup.env),
+        );
+        assert_eq!(
+            setup.client.try_claim_stipend(&setup.student, &id),
+            Err(Ok(Error::NothingToWithdraw))
+        );
+
+        setup.env.ledger().set_timestamp(3_500);
+        assert_eq!(setup.client.claim_stipend(&setup.student, &id), 200);
+
+        // Milestone bonuses come out of their own pool
+        assert_eq!(setup.complete(id, 1), Ok(400));
+        let balance = setup.client.get_balance(&id);
+        assert_eq!((balance.milestones, balance.stipend), (0, 300));
+
+        assert_eq!(
+            setup.client.try_close_scholarship(&setup.donor, &id),
+            Err(Ok(Error::DeadlineNotReached))
+        );
+
+        // Closing pays the periods still owed
+        setup.env.ledger().set_timestamp(6_000);
+        assert_eq!(setup.client.close_scholarship(&setup.donor, &id), 0);
+        assert_eq!(setup.token.balance(&setup.student), 1_000);
+        assert_eq!(setup.client.get_stipend(&id).paid_periods, 6);
+    }
+
+    #[test]
+    fn test_stipend_pool_is_filled_first() {
+        let setup = Setup::new();
+        let id = hybrid_scholarship(&setup);
+
+        setup.client.deposit_funds(&setup.donor, &id, &450);
+        let balance = setup.client.get_balance(&id);
+        assert_eq!((balance.milestones, balance.stipend), (0, 450));
+        assert_eq!(setup.complete(id, 1), Err(Error::InsufficientFunds));
+
+        // Only fully funded periods are paid
+        setup.env.ledger().set_timestamp(6_000);
+        assert_eq!(setup.client.claim_stipend(&setup.student, &id), 400);
+        assert_eq!(setup.client.get_balance(&id).stipend, 50);
+
+        // Once the stipend is covered, the rest goes to the milestones
+        setup.client.deposit_funds(&setup.donor, &id, &550);
+        let balance = setup.client.get_balance(&id);
+        assert_eq!((balance.milestones, balance.stipend), (400, 200));
+        assert_eq!(setup.complete(id, 1), Ok(400));
+        assert_eq!(setup.client.claim_stipend(&setup.student, &id), 200);
+    }
+
+    #[test]
+    fn test_stipend_added_after_deposit() {
+        let setup = Setup::new();
+        setup.env.ledger().set_timestamp(1_000);
+        let id = setup.client.create_scholarship(
+            &setup.donor,
+            &setup.student,
+            &1_000,
+            &setup.token.address,
+            &String::from_str(&setup.env, "USDC"),
+            &vec![&setup.env, create_milestone(&setup.env, 1, 400)],
+            &vec![&setup.env, setup.verifier.clone()],
+            &None,
+        );
+        setup.client.deposit_funds(&setup.donor, &id, &1_000);
+
+        // The surplus over the milestone rewards funds the stipend
+        setup
+            .client
+            .add_stipend(&setup.donor, &id, &100, &1_000, &6, &1_000);
+        let balance = setup.client.get_balance(&id);
+        assert_eq!((balance.milestones, balance.stipend), (400, 600));
+
+        assert_eq!(setup.client.claim_stipend(&setup.student, &id), 100);
+        assert_eq!(setup.complete(id, 1), Ok(400));
+
+        setup.env.ledger().set_timestamp(6_000);
+        assert_eq!(setup.client.close_scholarship(&setup.donor, &id), 0);
+        assert_eq!(setup.token.balance(&setup.student), 1_000);
+    }
+
+    #[test]
+    fn test_hybrid_cancel_pays_due_stipend() {
+        let setup = Setup::new();
+        let id = hybrid_scholarship(&setup);
+        setup.client.deposit_funds(&setup.donor, &id, &1_000);
+
+        setup.env.ledger().set_timestamp(2_000);
+        assert_eq!(setup.client.cancel_scholarship(&setup.donor, &id), 800);
+        assert_eq!(setup.token.balance(&setup.student), 200);
+        assert_eq!(setup.client.get_balance(&id).total, 0);
+    }
+
+    #[test]
+    fn test_error_stipend() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship_until(
+            &vec![&setup.env, create_milestone(&setup.env, 1, 400)],
+            Some(10_000),
+        );
+
+        // Milestones take the whole total
+        assert_eq!(
+            setup
+                .client
+                .try_add_stipend(&setup.donor, &id, &100, &1_000, &1, &0),
+            Err(Ok(Error::RewardsExceedTotal))
+        );
+        assert_eq!(
+            setup
+                .client
+                .try_add_stipend(&setup.student, &id, &100, &1_000, &1, &0),
+            Err(Ok(Error::NotDonor))
+        );
+        assert_eq!(
+            setup.client.try_claim_stipend(&setup.student, &id),
+            Err(Ok(Error::ScheduleNotFound))
+        );
+
+        let id = hybrid_scholarship(&setup);
+        assert_eq!(
+            setup.client.try_claim_stipend(&setup.donor, &id),
+            Err(Ok(Error::Unauthorized))
+        );
+        // One stipend per scholarship
+        assert_eq!(
+            setup
+                .client
+                .try_add_stipend(&setup.donor, &id, &1, &1_000, &1, &0),
+            Err(Ok(Error::InvalidSchedule))
+        );
+    }
+
+    #[test]
+    fn test_error_invalid_stipend_schedule() {
+        let setup = Setup::new();
+        let mut milestone = create_milestone(&setup.env, 1, 100);
+        milestone.deadline = Some(5_000);
+        let id = setup.client.create_scholarship(
+            &setup.donor,
+            &setup.student,
+            &1_000,
+            &setup.token.address,
+            &String::from_str(&setup.env, "USDC"),
+            &vec![&setup.env, milestone],
+            &vec![&setup.env, setup.verifier.clone()],
+            &Some(5_000),
+        );
+        let add = |amount: i128, period: u64, periods: u32| {
+            setup
+                .client
+                .try_add_stipend(&setup.donor, &id, &amount, &period, &periods, &1_000)
+        };
+
+        assert_eq!(add(0, 1_000, 3), Err(Ok(Error::InvalidAmount)));
+        assert_eq!(add(100, 0, 3), Err(Ok(Error::InvalidSchedule)));
+        assert_eq!(add(100, 1_000, 0), Err(Ok(Error::InvalidSchedule)));
+        // The last payment would fall after the end date
+        assert_eq!(add(100, 1_000, 6), Err(Ok(Error::InvalidSchedule)));
+        assert!(add(100, 1_000, 5).is_ok());
+    }
+
+    /// Register a fixed ed25519 key as an institution
+    fn institution(setup: &Setup, seed: u8) -> (SigningKey, BytesN<32>) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let public_key = public_key_of(&setup.env, &key);
+        setup.client.add_institution(&setup.admin, &public_key);
+        (key, public_key)
+    }
+
+    fn public_key_of(env: &Env, key: &SigningKey) -> BytesN<32> {
+        BytesN::from_array(env, &key.verifying_key().to_bytes())
+    }
+
+    /// Sign `attestation` for the escrow at `escrow`
+    fn sign(
+        env: &Env,
+        key: &SigningKey,
+        escrow: &Address,
+        attestation: &Attestation,
+    ) -> BytesN<64> {
+        let message: std::vec::Vec<u8> = (escrow.clone(), attestation.clone())
+            .to_xdr(env)
+            .iter()
+            .collect();
+        BytesN::from_array(env, &key.sign(&message).to_bytes())
+    }
+
+    fn attestation(setup: &Setup, scholarship_id: u64, milestone_id: u32) -> Attestation {
+        Attestation {
+            scholarship_id,
+            milestone_id,
+            student: setup.student.clone(),
+            score: 85,
+            expiry: 10_000,
+        }
+    }
+
+    #[test]
+    fn test_attested_milestone() {
+        let setup = Setup::new();
+        let (key, public_key) = institution(&setup, 7);
+        assert!(setup.client.is_institution(&public_key));
+
+        // The attestation counts as one of three approvals
+        let (id, verifiers) = quorum_scholarship(&setup, 3);
+        setup.client.add_attester(&setup.donor, &id, &public_key);
+        assert_eq!(
+            setup.client.get_attesters(&id),
+            vec![&setup.env, public_key.clone()]
+        );
+
+        let attestation = attestation(&setup, id, 1);
+        let signature = sign(&setup.env, &key, &setup.client.address, &attestation);
+        assert_eq!(
+            setup
+                .client
+                .try_complete_milestone_attested(&attestation, &public_key, &signature),
+            Err(Ok(Error::QuorumNotReached))
+        );
+        for verifier in &verifiers[..2] {
+            setup
+                .client
+                .approve_milestone(verifier, &id, &1, &proof(&setup.env));
+        }
+        assert_eq!(
+            setup
+                .client
+                .complete_milestone_attested(&attestation, &public_key, &signature),
+            400
+        );
+        assert_eq!(setup.token.balance(&setup.student), 400);
+        assert!(
+            setup
+                .client
+                .get_milestones(&id)
+                .get(0)
+                .unwrap()
+                .is_completed
+        );
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("attested"), id).into_val(&setup.env),
+            (1_u32, public_key.clone(), 85_u32).into_val(&setup.env),
+        );
+
+        assert_eq!(
+            setup.client.get_milestone_completion(&id, &1),
+            Some(MilestoneCompletion {
+                proof_hash: setup
+                    .env
+                    .crypto()
+                    .sha256(&(setup.client.address.clone(), attestation.clone()).to_xdr(&setup.env))
+                    .to_bytes(),
+                completed_by: Completer::Attester(public_key.clone()),
+                completed_at: 0,
+                score: Some(85),
+            })
+        );
+
+        // Replays are rejected
+        assert_eq!(
+            setup
+                .client
+                .try_complete_milestone_attested(&attestation, &public_key, &signature),
+            Err(Ok(Error::AttestationUsed))
+        );
+    }
+
+    #[test]
+    fn test_error_attestation_other_deployment() {
+        let setup = Setup::new();
+        let (key, public_key) = institution(&setup, 7);
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 400)]);
+        setup.client.deposit_funds(&setup.donor, &id, &400);
+        setup.client.add_attester(&setup.donor, &id, &public_key);
+
+        // A second escrow on the same progress contract trusting the same institution,
+        // with a scholarship under the same id for the same student
+        let other = ScholarshipEscrowClient::new(
+            &setup.env,
+            &setup.env.register_contract(None, ScholarshipEscrow),
+        );
+        setup.progress.add_escrow(&setup.admin, &other.address);
+        other.initialize(&setup.admin, &setup.progress.address);
+        other.add_institution(&setup.admin, &public_key);
+        let other_id = other.create_scholarship(
+            &setup.donor,
+            &setup.student,
+            &400,
+            &setup.token.address,
+            &String::from_str(&setup.env, "USDC"),
+            &vec![&setup.env, create_milestone(&setup.env, 1, 400)],
+            &vec![&setup.env, setup.verifier.clone()],
+            &None,
+        );
+        assert_eq!(other_id, id);
+        other.deposit_funds(&setup.donor, &other_id, &400);
+        other.add_attester(&setup.donor, &other_id, &public_key);
+
+        // An attestation signed for the first escrow does not verify on the second
+        let attestation = attestation(&setup, id, 1);
+        let signature = sign(&setup.env, &key, &setup.client.address, &attestation);
+        assert!(matches!(
+            other.try_complete_milestone_attested(&attestation, &public_key, &signature),
+            Err(Err(_))
+        ));
+        assert_eq!(setup.token.balance(&setup.student), 0);
+
+        setup
+            .client
+            .complete_milestone_attested(&attestation, &public_key, &signature);
+        assert_eq!(setup.token.balance(&setup.student), 400);
+    }
+
+    #[test]
+    fn test_error_attestation() {
+        let setup = Setup::new();
+        let (key, public_key) = institution(&setup, 7);
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            create_milestone(&setup.env, 1, 400),
+            create_milestone(&setup.env, 2, 600),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &1000);
+        let complete = |attestation: &Attestation, signer: &SigningKey, public_key: &BytesN<32>| {
+            let signature = sign(&setup.env, signer, &setup.client.address, attestation);
+            setup
+                .client
+                .try_complete_milestone_attested(attestation, public_key, &signature)
+        };
+
+        // Signed by an unknown key
+        let stranger = SigningKey::from_bytes(&[9; 32]);
+        let stranger_key = public_key_of(&setup.env, &stranger);
+        assert_eq!(
+            complete(&attestation(&setup, id, 1), &stranger, &stranger_key),
+            Err(Ok(Error::InstitutionNotFound))
+        );
+
+        // Signed by someone else than the registered key
+        assert!(matches!(
+            complete(&attestation(&setup, id, 1), &stranger, &public_key),
+            Err(Err(_))
+        ));
+
+        // From an institution the donor has not opted into
+        assert_eq!(
+            complete(&attestation(&setup, id, 1), &key, &public_key),
+            Err(Ok(Error::NotVerifier))
+        );
+        setup.client.add_attester(&setup.donor, &id, &public_key);
+
+        // For another student
+        let mut other = attestation(&setup, id, 1);
+        other.student = setup.donor.clone();
+        assert_eq!(
+            complete(&other, &key, &public_key),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        // The signature covers every field
+        let signed = attestation(&setup, id, 1);
+        let signature = sign(&setup.env, &key, &setup.client.address, &signed);
+        let mut tampered = signed.clone();
+        tampered.score = 100;
+        assert!(setup
+            .client
+            .try_complete_milestone_attested(&tampered, &public_key, &signature)
+            .is_err());
+
+        // The score must reach the milestone's required progress
+        let mut failed = attestation(&setup, id, 1);
+        failed.score = 20;
+        assert_eq!(
+            complete(&failed, &key, &public_key),
+            Err(Ok(Error::ProgressNotReached))
+        );
+
+        setup.env.ledger().set_timestamp(10_001);
+        assert_eq!(
+            complete(&attestation(&setup, id, 2), &key, &public_key),
+            Err(Ok(Error::AttestationExpired))
+        );
+
+        // The donor can opt out again
+        setup.env.ledger().set_timestamp(0);
+        setup.client.remove_attester(&setup.donor, &id, &public_key);
+        assert_eq!(
+            complete(&attestation(&setup, id, 1), &key, &public_key),
+            Err(Ok(Error::NotVerifier))
+        );
+
+        // Removing the key revokes its attestations
+        setup.client.add_attester(&setup.donor, &id, &public_key);
+        setup.client.remove_institution(&setup.admin, &public_key);
+        assert_eq!(
+            complete(&attestation(&setup, id, 1), &key, &public_key),
+            Err(Ok(Error::InstitutionNotFound))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_attestation_with_wrong_key_traps() {
+        let setup = Setup::new();
+        let (_, public_key) = institution(&setup, 7);
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 400)]);
+        setup.client.deposit_funds(&setup.donor, &id, &400);
+        setup.client.add_attester(&setup.donor, &id, &public_key);
 
-        let mut scholarship: Scholarship = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Scholarship(scholarship_id))
-            .expect("Scholarship not found");
+        // Signed by a key other than the one it claims
+        let stranger = SigningKey::from_bytes(&[9; 32]);
+        let attestation = attestation(&setup, id, 1);
+        let signature = sign(&setup.env, &stranger, &setup.client.address, &attestation);
+        setup
+            .client
+            .complete_milestone_attested(&attestation, &public_key, &signature);
+    }
 
-        if scholarship.donor != donor {
-            panic!("Only donor can cancel");
-        }
+    #[test]
+    fn test_error_institution_registry() {
+        let setup = Setup::new();
+        let (_, public_key) = institution(&setup, 7);
+
+        assert_eq!(
+            setup.client.try_add_institution(&setup.admin, &public_key),
+            Err(Ok(Error::InstitutionAlreadyExists))
+        );
+        assert_eq!(
+            setup.client.try_add_institution(&setup.donor, &public_key),
+            Err(Ok(Error::NotAdmin))
+        );
+
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 400)]);
+        setup.client.add_attester(&setup.donor, &id, &public_key);
+        assert_eq!(
+            setup
+                .client
+                .try_add_attester(&setup.donor, &id, &public_key),
+            Err(Ok(AttesterError::AttesterAlreadyExists.into()))
+        );
+        assert_eq!(
+            setup
+                .client
+                .try_add_attester(&setup.verifier, &id, &public_key),
+            Err(Ok(Error::NotDonor.into()))
+        );
+        setup.client.remove_attester(&setup.donor, &id, &public_key);
+        assert_eq!(
+            setup
+                .client
+                .try_remove_attester(&setup.donor, &id, &public_key),
+            Err(Ok(AttesterError::AttesterNotFound.into()))
+        );
+
+        setup.client.remove_institution(&setup.admin, &public_key);
+        assert!(!setup.client.is_institution(&public_key));
+        assert_eq!(
+            setup
+                .client
+                .try_add_attester(&setup.donor, &id, &public_key),
+            Err(Ok(Error::InstitutionNotFound.into()))
+        );
+        assert_eq!(
+            setup
+                .client
+                .try_remove_institution(&setup.admin, &public_key),
+            Err(Ok(Error::InstitutionNotFound))
+        );
+    }
+
+    #[test]
+    fn test_milestone_completion_record() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![
+            &setup.env,
+            create_milestone(&setup.env, 1, 400),
+            create_milestone(&setup.env, 2, 600),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &1000);
+
+        setup.env.ledger().set_timestamp(1_234);
+        assert_eq!(setup.complete(id, 1), Ok(400));
+        let proof = Bytes::from_slice(&setup.env, b"exam_score_85");
+        assert_eq!(
+            setup.client.get_milestone_completion(&id, &1),
+            Some(MilestoneCompletion {
+                proof_hash: setup.env.crypto().sha256(&proof).to_bytes(),
+                completed_by: Completer::Verifier(setup.verifier.clone()),
+                completed_at: 1_234,
+                score: None,
+            })
+        );
+        assert_eq!(setup.client.get_milestone_completion(&id, &2), None);
+    }
 
-        if scholarship.released_amount > 0 {
-            panic!("Cannot cancel scholarship with released payments");
+    #[test]
+    fn test_long_proof() {
+        let setup = Setup::new();
+        let id = setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 400)]);
+        setup.client.deposit_funds(&setup.donor, &id, &400);
+
+        // Proofs of any length are hashed byte for byte
+        let proof = [b'a'; 1_001];
+        assert_eq!(
+            setup.client.complete_milestone(
+                &setup.verifier,
+                &id,
+                &1,
+                &String::from_bytes(&setup.env, &proof),
+            ),
+            400
+        );
+        assert_eq!(
+            setup
+                .client
+                .get_milestone_completion(&id, &1)
+                .unwrap()
+                .proof_hash,
+            setup
+                .env
+                .crypto()
+                .sha256(&Bytes::from_slice(&setup.env, &proof))
+                .to_bytes()
+        );
+    }
+
+    fn result_leaf(env: &Env, student: &Address, score: u32) -> BytesN<32> {
+        let result = ExamResult {
+            student: student.clone(),
+            course_id: COURSE,
+            score,
+        };
+        env.crypto().sha256(&result.to_xdr(env)).to_bytes()
+    }
+
+    fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        let mut pair = Bytes::from(low.clone());
+        pair.append(&Bytes::from(high.clone()));
+        env.crypto().sha256(&pair).to_bytes()
+    }
+
+    /// Register an institution and have it post a batch with the student's score of 80
+    /// and another student's result.
+    /// Returns the institution, the batch id and the student's proof.
+    fn results_batch(setup: &Setup) -> (Address, u32, Vec<BytesN<32>>) {
+        let institution = Address::generate(&setup.env);
+        setup.progress.add_institution(&setup.admin, &institution);
+        let other = result_leaf(&setup.env, &Address::generate(&setup.env), 95);
+        let root = hash_pair(
+            &setup.env,
+            &result_leaf(&setup.env, &setup.student, 80),
+            &other,
+        );
+        let batch_id = setup.progress.post_results_root(&institution, &root);
+        (institution, batch_id, vec![&setup.env, other])
+    }
+
+    /// Scholarship with two course milestones needing 60% and 90%, taking results
+    /// from `institution`
+    fn course_scholarship(setup: &Setup, institution: &Address) -> u64 {
+        let mut milestones = vec![&setup.env];
+        for (id, required_progress) in [(1, 60), (2, 90)] {
+            let mut milestone = create_milestone(&setup.env, id, 500);
+            milestone.course_id = Some(COURSE);
+            milestone.required_progress = required_progress;
+            milestones.push_back(milestone);
         }
+        let id = setup.create_scholarship(&milestones);
+        setup.client.deposit_funds(&setup.donor, &id, &1000);
+        setup.client.add_verifier(&setup.donor, &id, institution);
+        id
+    }
 
-        scholarship.is_active = false;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Scholarship(scholarship_id), &scholarship);
+    #[test]
+    fn test_complete_milestone_with_result() {
+        let setup = Setup::new();
+        let (institution, batch_id, proof) = results_batch(&setup);
+        let id = course_scholarship(&setup, &institution);
+
+        // Reported progress is only 25%, the exam result proves 80%
+        assert_eq!(
+            setup.client.complete_milestone_with_result(
+                &institution,
+                &id,
+                &1,
+                &batch_id,
+                &80,
+                &proof
+            ),
+            500
+        );
+        assert_eq!(setup.token.balance(&setup.student), 500);
+        assert_last_event(
+            &setup.env,
+            &setup.client.address,
+            (symbol_short!("result"), id).into_val(&setup.env),
+            (1_u32, institution.clone(), batch_id, 80_u32).into_val(&setup.env),
+        );
+        assert_eq!(
+            setup.client.get_milestone_completion(&id, &1),
+            Some(MilestoneCompletion {
+                proof_hash: result_leaf(&setup.env, &setup.student, 80),
+                completed_by: Completer::Verifier(institution.clone()),
+                completed_at: 0,
+                score: Some(80),
+            })
+        );
+        // Nothing was reported, so nothing is locked in the progress contract
+        assert_eq!(
+            setup
+                .progress
+                .get_paid_progress(&setup.student, &Some(COURSE)),
+            0
+        );
+
+        // 80% is not enough for the second milestone
+        assert_eq!(
+            setup.client.try_complete_milestone_with_result(
+                &institution,
+                &id,
+                &2,
+                &batch_id,
+                &80,
+                &proof
+            ),
+            Err(Ok(Error::ProgressNotReached))
+        );
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::vec;
+    #[test]
+    fn test_result_counts_towards_quorum() {
+        let setup = Setup::new();
+        let (institution, batch_id, merkle_proof) = results_batch(&setup);
+        let id = course_scholarship(&setup, &institution);
+        setup.client.set_quorum(&setup.donor, &id, &2);
+
+        assert_eq!(
+            setup.client.try_complete_milestone_with_result(
+                &institution,
+                &id,
+                &1,
+                &batch_id,
+                &80,
+                &merkle_proof
+            ),
+            Err(Ok(Error::QuorumNotReached))
+        );
+        setup
+            .client
+            .approve_milestone(&setup.verifier, &id, &1, &proof(&setup.env));
+        assert_eq!(
+            setup.client.complete_milestone_with_result(
+                &institution,
+                &id,
+                &1,
+                &batch_id,
+                &80,
+                &merkle_proof
+            ),
+            500
+        );
+    }
 
     #[test]
-    fn test_create_scholarship() {
+    fn test_error_revoked_results_root() {
+        let setup = Setup::new();
+        let (institution, batch_id, proof) = results_batch(&setup);
+        let id = course_scholarship(&setup, &institution);
+
+        setup.progress.revoke_results_root(&institution, &batch_id);
+        assert_eq!(
+            setup.client.try_complete_milestone_with_result(
+                &institution,
+                &id,
+                &1,
+                &batch_id,
+                &80,
+                &proof
+            ),
+            Err(Ok(Error::InvalidProof))
+        );
+        assert_eq!(setup.token.balance(&setup.student), 0);
+    }
+
+    #[test]
+    fn test_error_invalid_result_proof() {
+        let setup = Setup::new();
+        let (institution, batch_id, proof) = results_batch(&setup);
+        let id = course_scholarship(&setup, &institution);
+        let complete =
+            |institution: &Address, id: u64, batch_id: u32, score: u32, proof: &Vec<BytesN<32>>| {
+                setup.client.try_complete_milestone_with_result(
+                    institution,
+                    &id,
+                    &1,
+                    &batch_id,
+                    &score,
+                    proof,
+                )
+            };
+
+        // Wrong score, unknown batch, empty proof
+        assert_eq!(
+            complete(&institution, id, batch_id, 95, &proof),
+            Err(Ok(Error::InvalidProof))
+        );
+        assert_eq!(
+            complete(&institution, id, batch_id + 1, 80, &proof),
+            Err(Ok(Error::InvalidProof))
+        );
+        assert_eq!(
+            complete(&institution, id, batch_id, 80, &Vec::new(&setup.env)),
+            Err(Ok(Error::InvalidProof))
+        );
+
+        // A verifier that did not post the batch
+        assert_eq!(
+            complete(&setup.verifier, id, batch_id, 80, &proof),
+            Err(Ok(Error::InvalidProof))
+        );
+
+        // Scholarships that did not add the institution as a verifier don't take its results
+        let other =
+            setup.create_scholarship(&vec![&setup.env, create_milestone(&setup.env, 1, 400)]);
+        assert_eq!(
+            complete(&institution, other, batch_id, 80, &proof),
+            Err(Ok(Error::NotVerifier))
+        );
+
+        // Milestones without a course can't be proven by an exam result
+        setup
+            .client
+            .add_verifier(&setup.donor, &other, &institution);
+        assert_eq!(
+            complete(&institution, other, batch_id, 80, &proof),
+            Err(Ok(Error::InvalidProof))
+        );
+    }
+
+    #[test]
+    fn test_events() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        let escrow = &setup.client.address;
+        let id = setup.create_scholarship(&vec![
+            env,
+            create_milestone(env, 1, 100),
+            create_milestone(env, 2, 300),
+        ]);
+
+        assert_last_event(
+            env,
+            escrow,
+            (symbol_short!("created"), id).into_val(env),
+            (
+                setup.donor.clone(),
+                setup.student.clone(),
+                400_i128,
+                setup.token.address.clone(),
+            )
+                .into_val(env),
+        );
+
+        setup.client.deposit_funds(&setup.donor, &id, &400);
+        assert_last_event(
+            env,
+            escrow,
+            (symbol_short!("deposit"), id).into_val(env),
+            (setup.donor.clone(), 400_i128, 400_i128).into_val(env),
+        );
+
+        setup.complete(id, 1).unwrap();
+        assert_last_event(
+            env,
+            escrow,
+            (symbol_short!("completed"), id).into_val(env),
+            (
+                1_u32,
+                setup.verifier.clone(),
+                setup.student.clone(),
+                100_i128,
+            )
+                .into_val(env),
+        );
+
+        let verifier2 = Address::generate(env);
+        setup.client.add_verifier(&setup.donor, &id, &verifier2);
+        assert_last_event(
+            env,
+            escrow,
+            (symbol_short!("ver_add"), id).into_val(env),
+            verifier2.into_val(env),
+        );
+
+        setup.client.remove_verifier(&setup.donor, &id, &verifier2);
+        assert_last_event(
+            env,
+            escrow,
+            (symbol_short!("ver_rm"), id).into_val(env),
+            verifier2.into_val(env),
+        );
+
+        setup.client.cancel_scholarship(&setup.donor, &id);
+        assert_last_event(
+            env,
+            escrow,
+            (symbol_short!("cancelled"), id).into_val(env),
+            (setup.donor.clone(), 300_i128).into_val(env),
+        );
+    }
+
+    #[test]
+    fn test_close_event() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        let id = setup.create_scholarship(&vec![env, create_milestone(env, 1, 100)]);
+        setup.client.deposit_funds(&setup.donor, &id, &150);
+        setup.complete(id, 1).unwrap();
+        setup.client.close_scholarship(&setup.donor, &id);
+
+        assert_last_event(
+            env,
+            &setup.client.address,
+            (symbol_short!("closed"), id).into_val(env),
+            (setup.donor.clone(), 50_i128).into_val(env),
+        );
+    }
+
+    #[test]
+    fn test_error_not_initialized() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ScholarshipEscrow);
         let client = ScholarshipEscrowClient::new(&env, &contract_id);
 
+        let admin = Address::generate(&env);
         let donor = Address::generate(&env);
         let student = Address::generate(&env);
-
-        let milestone1 = Milestone {
-            id: 1,
-            title: String::from_str(&env, "Complete Module 1"),
-            description: String::from_str(&env, "Finish first course module"),
-            required_progress: 25,
-            reward_amount: 250,
-            proof_type: String::from_str(&env, "exam"),
-            is_completed: false,
-        };
-
-        let milestones = vec![&env, milestone1];
+        let verifier = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
 
         env.mock_all_auths();
+        token_admin.mint(&donor, &100);
         let scholarship_id = client.create_scholarship(
             &donor,
             &student,
-            &1000,
-            &String::from_str(&env, "BRS"),
-            &milestones,
+            &100,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &vec![&env, create_milestone(&env, 1, 100)],
+            &vec![&env, verifier.clone()],
+            &None,
         );
+        client.deposit_funds(&donor, &scholarship_id, &100);
 
-        assert_eq!(scholarship_id, 1);
+        assert_eq!(
+            client.try_get_progress_contract(),
+            Err(Ok(Error::NotInitialized))
+        );
+        assert_eq!(
+            client.try_complete_milestone(
+                &verifier,
+                &scholarship_id,
+                &1,
+                &String::from_str(&env, "exam_score_85"),
+            ),
+            Err(Ok(Error::NotInitialized))
+        );
+    }
 
-        let scholarship = client.get_scholarship(&scholarship_id);
-        assert_eq!(scholarship.donor, donor);
-        assert_eq!(scholarship.student, student);
-        assert_eq!(scholarship.total_amount, 1000);
+    #[test]
+    fn test_error_already_initialized() {
+        let setup = Setup::new();
+
+        assert_eq!(
+            setup
+                .client
+                .try_initialize(&setup.donor, &setup.progress.address),
+            Err(Ok(Error::AlreadyInitialized))
+        );
     }
 
     #[test]
-    fn test_deposit_and_complete_milestone() {
+    fn test_error_not_admin() {
+        let setup = Setup::new();
+
+        assert_eq!(
+            setup
+                .client
+                .try_set_progress_contract(&setup.donor, &setup.progress.address),
+            Err(Ok(Error::NotAdmin))
+        );
+    }
+
+    #[test]
+    fn test_error_progress_not_reached() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        let mut milestone = create_milestone(env, 1, 100);
+        milestone.required_progress = 60;
+        let id = setup.create_scholarship(&vec![env, milestone]);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+
+        assert_eq!(setup.complete(id, 1), Err(Error::ProgressNotReached));
+    }
+
+    #[test]
+    fn test_progress_gates_milestones() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, ScholarshipEscrow);
-        let client = ScholarshipEscrowClient::new(&env, &contract_id);
+        env.mock_all_auths();
 
+        // Both contracts live in the same Env
+        let progress_id = env.register_contract(None, ScholarshipMilestone);
+        let progress = ScholarshipMilestoneClient::new(&env, &progress_id);
+        let escrow_id = env.register_contract(None, ScholarshipEscrow);
+        let escrow = ScholarshipEscrowClient::new(&env, &escrow_id);
+
+        let admin = Address::generate(&env);
         let donor = Address::generate(&env);
         let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let instructor = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&donor, &1000);
 
-        let milestone1 = Milestone {
-            id: 1,
-            title: String::from_str(&env, "Complete Module 1"),
-            description: String::from_str(&env, "Finish first course module"),
-            required_progress: 25,
-            reward_amount: 250,
-            proof_type: String::from_str(&env, "exam"),
-            is_completed: false,
-        };
+        progress.initialize(&admin);
+        progress.add_reporter(&admin, &instructor);
+        progress.add_course(&admin, &COURSE, &String::from_str(&env, "Algorithms"), &1);
+        progress.add_escrow(&admin, &escrow_id);
+        escrow.initialize(&admin, &progress_id);
+        assert_eq!(escrow.get_progress_contract(), progress_id);
 
-        let milestones = vec![&env, milestone1];
+        let mut module1 = create_milestone(&env, 1, 400);
+        module1.required_progress = 50;
+        let mut module2 = create_milestone(&env, 2, 600);
+        module2.required_progress = 100;
+        let scholarship_id = escrow.create_scholarship(
+            &donor,
+            &student,
+            &1000,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &vec![&env, module1, module2],
+            &vec![&env, verifier.clone()],
+            &None,
+        );
+        escrow.deposit_funds(&donor, &scholarship_id, &1000);
+
+        let proof = String::from_str(&env, "exam_score_85");
+
+        // No progress reported yet
+        assert_eq!(
+            escrow.try_complete_milestone(&verifier, &scholarship_id, &1, &proof),
+            Err(Ok(Error::ProgressNotReached))
+        );
 
+        progress.update_progress(&instructor, &student, &COURSE, &50, &evidence(&env));
+        assert_eq!(
+            escrow.complete_milestone(&verifier, &scholarship_id, &1, &proof),
+            400
+        );
+        assert_eq!(
+            escrow.try_complete_milestone(&verifier, &scholarship_id, &2, &proof),
+            Err(Ok(Error::ProgressNotReached))
+        );
+
+        progress.update_progress(&instructor, &student, &COURSE, &50, &evidence(&env));
+        assert_eq!(
+            escrow.complete_milestone(&verifier, &scholarship_id, &2, &proof),
+            600
+        );
+        assert_eq!(token.balance(&student), 1000);
+        assert_eq!(escrow.get_completion_percentage(&scholarship_id), 100);
+    }
+
+    #[test]
+    fn test_course_gates_milestone() {
+        let env = Env::default();
         env.mock_all_auths();
-        let scholarship_id = client.create_scholarship(
+
+        let progress_id = env.register_contract(None, ScholarshipMilestone);
+        let progress = ScholarshipMilestoneClient::new(&env, &progress_id);
+        let escrow_id = env.register_contract(None, ScholarshipEscrow);
+        let escrow = ScholarshipEscrowClient::new(&env, &escrow_id);
+
+        let admin = Address::generate(&env);
+        let donor = Address::generate(&env);
+        let student = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let instructor = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        token_admin.mint(&donor, &500);
+
+        let thesis = 2;
+        progress.initialize(&admin);
+        progress.add_reporter(&admin, &instructor);
+        progress.add_course(&admin, &COURSE, &String::from_str(&env, "Algorithms"), &1);
+        progress.add_escrow(&admin, &escrow_id);
+        progress.add_course(&admin, &thesis, &String::from_str(&env, "Thesis"), &1);
+        escrow.initialize(&admin, &progress_id);
+
+        let mut milestone = create_milestone(&env, 1, 500);
+        milestone.required_progress = 50;
+        milestone.course_id = Some(thesis);
+        let scholarship_id = escrow.create_scholarship(
             &donor,
             &student,
-            &1000,
-            &String::from_str(&env, "BRS"),
+            &500,
+            &token.address,
+            &String::from_str(&env, "USDC"),
+            &vec![&env, milestone],
+            &vec![&env, verifier.clone()],
+            &None,
+        );
+        escrow.deposit_funds(&donor, &scholarship_id, &500);
+
+        let proof = String::from_str(&env, "thesis_draft");
+
+        // Progress in another course does not count
+        progress.update_progress(&instructor, &student, &COURSE, &100, &evidence(&env));
+        assert_eq!(
+            escrow.try_complete_milestone(&verifier, &scholarship_id, &1, &proof),
+            Err(Ok(Error::ProgressNotReached))
+        );
+
+        progress.update_progress(&instructor, &student, &thesis, &50, &evidence(&env));
+        assert_eq!(
+            escrow.complete_milestone(&verifier, &scholarship_id, &1, &proof),
+            500
+        );
+        assert_eq!(token.balance(&student), 500);
+    }
+
+    #[test]
+    fn test_error_escrow_not_registered() {
+        let setup = Setup::new();
+        let env = &setup.env;
+
+        // A second escrow the progress admin never registered
+        let unregistered =
+            ScholarshipEscrowClient::new(env, &env.register_contract(None, ScholarshipEscrow));
+        assert_eq!(
+            unregistered.try_initialize(&setup.admin, &setup.progress.address),
+            Err(Ok(Error::EscrowNotRegistered))
+        );
+
+        // Dropped from the registry after initialization: payouts that need
+        // progress fail with a clear error instead of the progress contract's code
+        let id = setup.create_scholarship(&vec![env, create_milestone(env, 1, 100)]);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+        setup
+            .progress
+            .remove_escrow(&setup.admin, &setup.client.address);
+        assert_eq!(setup.complete(id, 1), Err(Error::EscrowNotRegistered));
+        assert_eq!(
+            setup
+                .client
+                .try_set_progress_contract(&setup.admin, &setup.progress.address),
+            Err(Ok(Error::EscrowNotRegistered))
+        );
+
+        setup
+            .progress
+            .add_escrow(&setup.admin, &setup.client.address);
+        assert_eq!(setup.complete(id, 1), Ok(100));
+    }
+
+    #[test]
+    fn test_paid_progress_cannot_be_revoked() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        let id = setup.create_scholarship(&vec![env, create_milestone(env, 1, 100)]);
+        setup.client.deposit_funds(&setup.donor, &id, &100);
+
+        setup.complete(id, 1).unwrap();
+        assert_eq!(setup.progress.get_paid_progress(&setup.student, &None), 25);
+
+        // The 25% the payout relied on can no longer be taken back by the reporter
+        assert_eq!(
+            setup.progress.try_revoke_progress(
+                &setup.reporter,
+                &setup.student,
+                &0,
+                &ReasonCode::DataEntryError,
+                &String::from_str(env, "wrong student"),
+            ),
+            Err(Ok(ProgressError::ProgressAlreadyPaid))
+        );
+        assert_eq!(
+            setup
+                .progress
+                .get_student_info(&setup.student)
+                .unwrap()
+                .total_progress,
+            25
+        );
+    }
+
+    #[test]
+    fn test_milestone_validation() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        let create = |total: i128, milestones: Vec<Milestone>| {
+            setup.client.try_create_scholarship(
+                &setup.donor,
+                &setup.student,
+                &total,
+                &setup.token.address,
+                &String::from_str(env, "USDC"),
+                &milestones,
+                &vec![env, setup.verifier.clone()],
+                &None,
+            )
+        };
+
+        assert_eq!(
+            create(100, vec![env, create_milestone(env, 1, 100)]),
+            Ok(Ok(1))
+        );
+
+        // Rewards may leave part of the total unallocated
+        assert_eq!(
+            create(1000, vec![env, create_milestone(env, 1, 250)]),
+            Ok(Ok(2))
+        );
+
+        assert_eq!(
+            create(0, vec![env, create_milestone(env, 1, 100)]),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(create(100, Vec::new(env)), Err(Ok(Error::NoMilestones)));
+
+        let mut too_many = Vec::new(env);
+        for id in 0..=MAX_MILESTONES {
+            too_many.push_back(create_milestone(env, id, 1));
+        }
+        assert_eq!(create(1000, too_many), Err(Ok(Error::TooManyMilestones)));
+
+        assert_eq!(
+            create(
+                300,
+                vec![
+                    env,
+                    create_milestone(env, 1, 100),
+                    create_milestone(env, 2, 100),
+                    create_milestone(env, 1, 100),
+                ]
+            ),
+            Err(Ok(Error::DuplicateMilestoneId))
+        );
+
+        assert_eq!(
+            create(100, vec![env, create_milestone(env, 1, 0)]),
+            Err(Ok(Error::InvalidReward))
+        );
+        assert_eq!(
+            create(100, vec![env, create_milestone(env, 1, -50)]),
+            Err(Ok(Error::InvalidReward))
+        );
+
+        let mut completed = create_milestone(env, 1, 100);
+        completed.is_completed = true;
+        assert_eq!(
+            create(100, vec![env, completed]),
+            Err(Ok(Error::MilestoneAlreadyCompleted))
+        );
+
+        assert_eq!(
+            create(
+                150,
+                vec![
+                    env,
+                    create_milestone(env, 1, 100),
+                    create_milestone(env, 2, 100),
+                ]
+            ),
+            Err(Ok(Error::RewardsExceedTotal))
+        );
+
+        assert_eq!(setup.client.get_scholarship_count(), 2);
+    }
+
+    #[test]
+    fn test_indexed_lookups() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        let other_student = Address::generate(env);
+        let milestones = vec![env, create_milestone(env, 1, 100)];
+
+        let id1 = setup.create_scholarship(&milestones);
+        let id2 = setup.client.create_scholarship(
+            &setup.donor,
+            &other_student,
+            &100,
+            &setup.token.address,
+            &String::from_str(env, "USDC"),
             &milestones,
+            &vec![env, setup.verifier.clone()],
+            &None,
         );
+        let id3 = setup.create_scholarship(&milestones);
+        setup.client.cancel_scholarship(&setup.donor, &id3);
 
-        // Deposit funds
-        client.deposit_funds(&donor, &scholarship_id, &1000);
-        assert_eq!(client.get_balance(&scholarship_id), 1000);
+        let student_ids: std::vec::Vec<u64> = setup
+            .client
+            .get_student_scholarships(&setup.student)
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(student_ids, [id1, id3]);
+        assert_eq!(
+            setup.client.get_student_scholarship_count(&setup.student),
+            2
+        );
+        assert_eq!(
+            setup.client.get_student_scholarship_count(&other_student),
+            1
+        );
+        assert_eq!(setup.client.get_donor_scholarship_count(&setup.donor), 3);
 
-        // Complete milestone
-        let proof = String::from_str(&env, "exam_score_85");
-        let released = client.complete_milestone(&scholarship_id, &1, &proof);
+        // Cancelled scholarships stay listed as inactive
+        let student_page = setup
+            .client
+            .get_student_scholarships_page(&setup.student, &1, &10);
+        assert_eq!(student_page.len(), 1);
+        assert_eq!(student_page.get(0).unwrap().id, id3);
+        assert!(!student_page.get(0).unwrap().is_active);
 
-        assert_eq!(released, 250);
-        assert_eq!(client.get_balance(&scholarship_id), 750);
+        let donor_page = setup
+            .client
+            .get_donor_scholarships_page(&setup.donor, &0, &2);
+        assert_eq!(donor_page.len(), 2);
+        assert_eq!(donor_page.get(0).unwrap().id, id1);
+        assert_eq!(donor_page.get(1).unwrap().id, id2);
 
-        let scholarship = client.get_scholarship(&scholarship_id);
-        assert_eq!(scholarship.released_amount, 250);
+        let all_page = setup.client.get_scholarships_page(&1, &5);
+        assert_eq!(all_page.len(), 2);
+        assert_eq!(all_page.get(0).unwrap().id, id2);
+
+        // Out of range pages are empty
+        assert_eq!(
+            setup
+                .client
+                .get_donor_scholarships_page(&setup.donor, &3, &10)
+                .len(),
+            0
+        );
+        assert_eq!(setup.client.get_scholarships_page(&3, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_page_cost_is_constant() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        env.budget().reset_unlimited();
+
+        let milestones = vec![env, create_milestone(env, 1, 100)];
+        let create_for_others = |count: u32| {
+            for _ in 0..count {
+                setup.client.create_scholarship(
+                    &Address::generate(env),
+                    &Address::generate(env),
+                    &100,
+                    &setup.token.address,
+                    &String::from_str(env, "USDC"),
+                    &milestones,
+                    &vec![env, setup.verifier.clone()],
+                    &None,
+                );
+            }
+        };
+        let page_cost = |offset: u32| {
+            env.budget().reset_default();
+            let page = setup
+                .client
+                .get_student_scholarships_page(&setup.student, &offset, &5);
+            assert_eq!(page.len(), 5);
+            let cost = env.budget().cpu_instruction_cost();
+            env.budget().reset_unlimited();
+            cost
+        };
+
+        for _ in 0..20 {
+            setup.create_scholarship(&milestones);
+        }
+        create_for_others(10);
+        let small = page_cost(0);
+
+        create_for_others(100);
+        let large = page_cost(0);
+        let deep = page_cost(15);
+
+        // Cost depends on the page, not on how many scholarships exist. The
+        // test host's storage map lookups are logarithmic, hence the tolerance
+        // (a linear scan over 4x the scholarships would cost several times more).
+        assert!(large <= small + small / 4, "{small} vs {large}");
+        assert!(deep <= large + large / 20, "{large} vs {deep}");
+    }
+
+    #[test]
+    fn test_storage_ttl_extended_on_touch() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        let escrow = setup.client.address.clone();
+        let id = setup.create_scholarship(&vec![env, create_milestone(env, 1, 100)]);
+
+        let ttls = || {
+            env.as_contract(&escrow, || {
+                (
+                    env.storage().instance().get_ttl(),
+                    env.storage()
+                        .persistent()
+                        .get_ttl(&DataKey::Scholarship(id)),
+                    env.storage().persistent().get_ttl(&DataKey::Balance(id)),
+                    env.storage()
+                        .persistent()
+                        .get_ttl(&DataKey::StudentScholarship(setup.student.clone(), 0)),
+                )
+            })
+        };
+        assert_eq!(
+            ttls(),
+            (
+                INSTANCE_BUMP_AMOUNT,
+                PERSISTENT_BUMP_AMOUNT,
+                PERSISTENT_BUMP_AMOUNT,
+                PERSISTENT_BUMP_AMOUNT,
+            )
+        );
+
+        // Once below the threshold, any touch restores the full TTL
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 10 * DAY_IN_LEDGERS);
+        setup
+            .client
+            .add_verifier(&setup.donor, &id, &Address::generate(env));
+        let (instance_ttl, scholarship_ttl, balance_ttl, _) = ttls();
+        assert_eq!(instance_ttl, INSTANCE_BUMP_AMOUNT);
+        assert_eq!(scholarship_ttl, PERSISTENT_BUMP_AMOUNT);
+        assert_eq!(balance_ttl, PERSISTENT_BUMP_AMOUNT);
+    }
+
+    #[test]
+    fn test_bump_keeps_scholarship_alive() {
+        let setup = Setup::new();
+        let env = &setup.env;
+        env.budget().reset_unlimited();
+        let id = setup.create_scholarship(&vec![
+            env,
+            create_milestone(env, 1, 100),
+            create_milestone(env, 2, 100),
+            create_milestone(env, 3, 100),
+        ]);
+        setup.client.deposit_funds(&setup.donor, &id, &300);
+        let (pool_id, sponsor) = matching_pool(&setup, 100, 10_000, 100);
+        setup.client.attach_matching_pool(&sponsor, &pool_id, &id);
+
+        // A completion record, a pending approval and a dispute
+        let second = Address::generate(env);
+        setup.client.add_verifier(&setup.donor, &id, &second);
+        setup.client.set_quorum(&setup.donor, &id, &2);
+        setup
+            .client
+            .approve_milestone(&second, &id, &1, &proof(env));
+        assert_eq!(setup.complete(id, 1), Ok(100));
+        setup
+            .client
+            .approve_milestone(&second, &id, &2, &proof(env));
+        setup
+            .client
+            .set_arbitrator(&setup.admin, &Address::generate(env));
+        setup
+            .client
+            .open_dispute(&setup.student, &id, &3, &reason(env));
+
+        // A four-year degree, with a keeper bumping every 25 days. That is far
+        // past both the default TTL and a single bump amount.
+        let keeper = ScholarshipEscrowClient::new(env, &setup.client.address);
+        for _ in 0..60 {
+            env.ledger()
+                .set_sequence_number(env.ledger().sequence() + 25 * DAY_IN_LEDGERS);
+            keeper.bump(&id);
+        }
+
+        let scholarship = setup.client.get_scholarship(&id);
+        assert!(scholarship.is_active);
+        assert_eq!(setup.client.get_balance(&id).total, 200);
+        assert_eq!(setup.client.get_milestones(&id).len(), 3);
+        assert_eq!(setup.client.get_verifiers(&id).len(), 2);
+        env.as_contract(&setup.client.address, || {
+            for key in [
+                DataKey::Contributions(id),
+                DataKey::Completion(id, 1),
+                DataKey::Approvals(id, 2),
+                DataKey::Dispute(id, 3),
+                DataKey::StudentScholarshipCount(setup.student.clone()),
+                DataKey::StudentScholarship(setup.student.clone(), 0),
+                DataKey::DonorScholarshipCount(setup.donor.clone()),
+                DataKey::DonorScholarship(setup.donor.clone(), 0),
+                DataKey::MatchingPool(pool_id),
+            ] {
+                assert_eq!(
+                    env.storage().persistent().get_ttl(&key),
+                    PERSISTENT_BUMP_AMOUNT
+                );
+            }
+        });
+
+        // The student and donor indexes still take new scholarships and list the old one
+        let next = setup.create_scholarship(&vec![env, create_milestone(env, 1, 100)]);
+        assert_eq!(
+            setup.client.get_student_scholarships(&setup.student),
+            vec![
+                env,
+                setup.client.get_scholarship(&id),
+                setup.client.get_scholarship(&next)
+            ]
+        );
+        assert_eq!(setup.client.get_donor_scholarship_count(&setup.donor), 2);
+        assert_eq!(setup.client.get_matching_pool(&pool_id).remaining, 100);
+        assert_eq!(
+            setup.client.try_bump(&42),
+            Err(Ok(Error::ScholarshipNotFound))
+        );
     }
 }
@@ -1,6 +1,44 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env, String, Symbol};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short,
+    token::TokenInterface, Address, Env, String,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InsufficientBalance = 4,
+    InvalidProgress = 5,
+    InsufficientAllowance = 6,
+    InvalidExpiration = 7,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AllowanceDataKey {
+    pub from: Address,
+    pub spender: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+const DAY_IN_LEDGERS: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const BALANCE_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - 7 * DAY_IN_LEDGERS;
 
+// Token metadata and supply live in instance storage, balances are persistent
+// and extended whenever they are touched.
 #[contracttype]
 pub enum DataKey {
     Admin,
@@ -9,6 +47,7 @@ pub enum DataKey {
     Decimals,
     TotalSupply,
     Balance(Address),
+    Allowance(AllowanceDataKey), // temporary storage, lives until expiration_ledger
 }
 
 #[contract]
@@ -17,155 +56,339 @@ pub struct ScholarshipToken;
 #[contractimpl]
 impl ScholarshipToken {
     /// Initialize the BRS Token
-    pub fn initialize(env: Env, admin: Address, name: String, symbol: String) {
-        if env.storage().persistent().has(&DataKey::Admin) {
-            panic!("Already initialized");
+    pub fn initialize(env: Env, admin: Address, name: String, symbol: String) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
         }
 
-        env.storage().persistent().set(&DataKey::Admin, &admin);
-        env.storage().persistent().set(&DataKey::Name, &name);
-        env.storage().persistent().set(&DataKey::Symbol, &symbol);
-        env.storage().persistent().set(&DataKey::Decimals, &7_u32);
-        env.storage().persistent().set(&DataKey::TotalSupply, &0_i128);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Name, &name);
+        env.storage().instance().set(&DataKey::Symbol, &symbol);
+        env.storage().instance().set(&DataKey::Decimals, &7_u32);
+        env.storage().instance().set(&DataKey::TotalSupply, &0_i128);
+        Self::extend_instance(&env);
+
+        Ok(())
     }
 
     /// Mint new BRS tokens (Admin only)
-    pub fn mint(env: Env, to: Address, amount: i128) {
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .unwrap();
+    /// Emits `("mint", admin, to)` with `amount`
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), Error> {
+        let admin = Self::admin(&env)?;
         admin.require_auth();
+        Self::extend_instance(&env);
 
         if amount <= 0 {
-            panic!("Amount must be positive");
+            return Err(Error::InvalidAmount);
         }
 
-        // Update recipient balance
-        let balance_key = DataKey::Balance(to.clone());
-        let current_balance: i128 = env
-            .storage()
-            .persistent()
-            .get(&balance_key)
-            .unwrap_or(0);
-        let new_balance = current_balance + amount;
-        env.storage().persistent().set(&balance_key, &new_balance);
+        Self::receive_balance(&env, &to, amount);
+        Self::adjust_total_supply(&env, amount);
 
-        // Update total supply
-        let total_supply: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
+        env.events()
+            .publish((symbol_short!("mint"), admin, to), amount);
+
+        Ok(())
+    }
+
+    /// Get balance of an address
+    /// Kept for existing callers, same as SEP-41 `balance`
+    pub fn balance_of(env: Env, address: Address) -> i128 {
+        Self::read_balance(&env, &address)
+    }
+
+    /// Get total supply
+    pub fn total_supply(env: Env) -> i128 {
         env.storage()
-            .persistent()
-            .set(&DataKey::TotalSupply, &(total_supply + amount));
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
     }
 
-    /// Transfer BRS tokens
-    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
-        from.require_auth();
+    /// Distribute tokens based on progress (0-100)
+    /// Returns amount of tokens minted
+    /// Emits `("distrib", admin, student)` with `(progress, amount)` when tokens are minted
+    pub fn distribute_for_progress(
+        env: Env,
+        student: Address,
+        progress: u32,
+    ) -> Result<i128, Error> {
+        let admin = Self::admin(&env)?;
+        admin.require_auth();
+        Self::extend_instance(&env);
 
-        if amount <= 0 {
-            panic!("Amount must be positive");
+        if progress > 100 {
+            return Err(Error::InvalidProgress);
         }
 
-        // Check from balance
-        let from_key = DataKey::Balance(from.clone());
-        let from_balance: i128 = env
-            .storage()
-            .persistent()
-            .get(&from_key)
-            .unwrap_or(0);
+        // 1% progress = 1 BRS token
+        let amount = progress as i128;
+
+        if amount > 0 {
+            Self::receive_balance(&env, &student, amount);
+            Self::adjust_total_supply(&env, amount);
+
+            env.events().publish(
+                (symbol_short!("distrib"), admin, student),
+                (progress, amount),
+            );
+        }
+
+        Ok(amount)
+    }
+}
+
+/// SEP-41 token interface, so wallets and the escrow can treat BRS like any Stellar token.
+/// Failures panic with `Error` codes since the interface has no `Result` return types.
+#[contractimpl]
+impl TokenInterface for ScholarshipToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        Self::read_allowance(&env, &from, &spender).amount
+    }
+
+    /// Emits `("approve", from, spender)` with `(amount, expiration_ledger)`
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+        Self::extend_instance(&env);
 
-        if from_balance < amount {
-            panic!("Insufficient balance");
+        if amount < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
         }
 
-        // Update from balance
+        Self::write_allowance(&env, &from, &spender, amount, expiration_ledger);
+
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        Self::read_balance(&env, &id)
+    }
+
+    /// Emits `("transfer", from, to)` with `amount`
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        Self::extend_instance(&env);
+
+        Self::check_amount(&env, amount);
+        Self::spend_balance(&env, &from, amount);
+        Self::receive_balance(&env, &to, amount);
+
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+    }
+
+    /// Emits `("transfer", from, to)` with `amount`
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        Self::extend_instance(&env);
+
+        Self::check_amount(&env, amount);
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::spend_balance(&env, &from, amount);
+        Self::receive_balance(&env, &to, amount);
+
+        env.events()
+            .publish((symbol_short!("transfer"), from, to), amount);
+    }
+
+    /// Emits `("burn", from)` with `amount`
+    fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        Self::extend_instance(&env);
+
+        Self::check_amount(&env, amount);
+        Self::spend_balance(&env, &from, amount);
+        Self::adjust_total_supply(&env, -amount);
+
+        env.events().publish((symbol_short!("burn"), from), amount);
+    }
+
+    /// Emits `("burn", from)` with `amount`
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        Self::extend_instance(&env);
+
+        Self::check_amount(&env, amount);
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::spend_balance(&env, &from, amount);
+        Self::adjust_total_supply(&env, -amount);
+
+        env.events().publish((symbol_short!("burn"), from), amount);
+    }
+
+    fn decimals(env: Env) -> u32 {
         env.storage()
-            .persistent()
-            .set(&from_key, &(from_balance - amount));
+            .instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
+    }
 
-        // Update to balance
-        let to_key = DataKey::Balance(to.clone());
-        let to_balance: i128 = env.storage().persistent().get(&to_key).unwrap_or(0);
+    fn name(env: Env) -> String {
         env.storage()
-            .persistent()
-            .set(&to_key, &(to_balance + amount));
+            .instance()
+            .get(&DataKey::Name)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
     }
 
-    /// Get balance of an address
-    pub fn balance_of(env: Env, address: Address) -> i128 {
-        let balance_key = DataKey::Balance(address);
-        env.storage().persistent().get(&balance_key).unwrap_or(0)
+    fn symbol(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::Symbol)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotInitialized))
     }
+}
 
-    /// Get total supply
-    pub fn total_supply(env: Env) -> i128 {
+impl ScholarshipToken {
+    fn admin(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// Transfers and burns of zero are allowed, as with the SAC
+    fn check_amount(env: &Env, amount: i128) {
+        if amount < 0 {
+            panic_with_error!(env, Error::InvalidAmount);
+        }
+    }
+
+    fn extend_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    fn read_balance(env: &Env, address: &Address) -> i128 {
+        let key = DataKey::Balance(address.clone());
+        match env.storage().persistent().get::<_, i128>(&key) {
+            Some(balance) => {
+                Self::extend_balance(env, &key);
+                balance
+            }
+            None => 0,
+        }
+    }
+
+    fn write_balance(env: &Env, address: &Address, balance: i128) {
+        let key = DataKey::Balance(address.clone());
+        env.storage().persistent().set(&key, &balance);
+        Self::extend_balance(env, &key);
+    }
+
+    fn extend_balance(env: &Env, key: &DataKey) {
         env.storage()
             .persistent()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0)
+            .extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
     }
 
-    /// Get token name
-    pub fn name(env: Env) -> String {
-        env.storage().persistent().get(&DataKey::Name).unwrap()
+    fn receive_balance(env: &Env, address: &Address, amount: i128) {
+        let balance = Self::read_balance(env, address);
+        Self::write_balance(env, address, balance + amount);
     }
 
-    /// Get token symbol
-    pub fn symbol(env: Env) -> String {
-        env.storage().persistent().get(&DataKey::Symbol).unwrap()
+    fn spend_balance(env: &Env, address: &Address, amount: i128) {
+        let balance = Self::read_balance(env, address);
+        if balance < amount {
+            panic_with_error!(env, Error::InsufficientBalance);
+        }
+        Self::write_balance(env, address, balance - amount);
     }
 
-    /// Distribute tokens based on progress (0-100)
-    /// Returns amount of tokens minted
-    pub fn distribute_for_progress(env: Env, student: Address, progress: u32) -> i128 {
-        let admin: Address = env
+    fn adjust_total_supply(env: &Env, delta: i128) {
+        let total_supply: i128 = env
             .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .unwrap();
-        admin.require_auth();
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply, &(total_supply + delta));
+    }
 
-        if progress > 100 {
-            panic!("Progress cannot exceed 100");
+    /// Expired allowances read as zero
+    fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: from.clone(),
+            spender: spender.clone(),
+        });
+        match env.storage().temporary().get::<_, AllowanceValue>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            Some(allowance) => AllowanceValue {
+                amount: 0,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+            None => AllowanceValue {
+                amount: 0,
+                expiration_ledger: 0,
+            },
         }
+    }
 
-        // 1% progress = 1 BRS token
-        let amount = progress as i128;
+    fn write_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        let current_ledger = env.ledger().sequence();
+        if amount > 0 && expiration_ledger < current_ledger {
+            panic_with_error!(env, Error::InvalidExpiration);
+        }
 
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: from.clone(),
+            spender: spender.clone(),
+        });
+        env.storage().temporary().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+
+        // Keep the entry alive exactly as long as the allowance is valid
         if amount > 0 {
-            let balance_key = DataKey::Balance(student.clone());
-            let current_balance: i128 = env
-                .storage()
-                .persistent()
-                .get(&balance_key)
-                .unwrap_or(0);
-            let new_balance = current_balance + amount;
-            env.storage().persistent().set(&balance_key, &new_balance);
-
-            // Update total supply
-            let total_supply: i128 = env
-                .storage()
-                .persistent()
-                .get(&DataKey::TotalSupply)
-                .unwrap_or(0);
+            let live_for = expiration_ledger - current_ledger;
             env.storage()
-                .persistent()
-                .set(&DataKey::TotalSupply, &(total_supply + amount));
+                .temporary()
+                .extend_ttl(&key, live_for, live_for);
         }
+    }
 
-        amount
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let allowance = Self::read_allowance(env, from, spender);
+        if allowance.amount < amount {
+            panic_with_error!(env, Error::InsufficientAllowance);
+        }
+        if amount > 0 {
+            Self::write_allowance(
+                env,
+                from,
+                spender,
+                allowance.amount - amount,
+                allowance.expiration_ledger,
+            );
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+    use soroban_sdk::testutils::{Address as _, Events, Ledger};
+    use soroban_sdk::{token, vec, IntoVal, Val, Vec};
+
+    fn last_event(env: &Env) -> Vec<(Address, Vec<Val>, Val)> {
+        let events = env.events().all();
+        events.slice(events.len() - 1..)
+    }
 
     #[test]
     fn test_token_initialization() {
@@ -240,7 +463,7 @@ mod test {
         client.initialize(&admin, &name, &symbol);
 
         env.mock_all_auths();
-        
+
         // 25% progress = 25 BRS
         let minted = client.distribute_for_progress(&student, &25);
         assert_eq!(minted, 25);
@@ -251,4 +474,363 @@ mod test {
         assert_eq!(minted2, 30);
         assert_eq!(client.balance_of(&student), 55);
     }
+
+    #[test]
+    fn test_error_already_initialized() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+        assert_eq!(
+            client.try_initialize(&admin, &name, &symbol),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_error_not_initialized() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let student = Address::generate(&env);
+
+        env.mock_all_auths();
+        assert_eq!(client.try_name(), Err(Ok(Error::NotInitialized.into())));
+        assert_eq!(client.try_symbol(), Err(Ok(Error::NotInitialized.into())));
+        assert_eq!(client.try_decimals(), Err(Ok(Error::NotInitialized.into())));
+        assert_eq!(
+            client.try_mint(&student, &100),
+            Err(Ok(Error::NotInitialized))
+        );
+        assert_eq!(
+            client.try_distribute_for_progress(&student, &10),
+            Err(Ok(Error::NotInitialized))
+        );
+    }
+
+    #[test]
+    fn test_error_invalid_amount() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let student1 = Address::generate(&env);
+        let student2 = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+
+        env.mock_all_auths();
+        assert_eq!(
+            client.try_mint(&student1, &0),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_transfer(&student1, &student2, &-1),
+            Err(Ok(Error::InvalidAmount.into()))
+        );
+        assert_eq!(
+            client.try_burn(&student1, &-1),
+            Err(Ok(Error::InvalidAmount.into()))
+        );
+
+        // Zero amounts go through without an allowance or a balance
+        client.transfer(&student1, &student2, &0);
+        client.transfer_from(&student2, &student1, &student2, &0);
+        client.burn(&student1, &0);
+        assert_eq!(client.balance(&student2), 0);
+        assert_eq!(client.allowance(&student1, &student2), 0);
+        assert_eq!(client.total_supply(), 0);
+    }
+
+    #[test]
+    fn test_error_insufficient_balance() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let student1 = Address::generate(&env);
+        let student2 = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+
+        env.mock_all_auths();
+        client.mint(&student1, &10);
+        assert_eq!(
+            client.try_transfer(&student1, &student2, &11),
+            Err(Ok(Error::InsufficientBalance.into()))
+        );
+    }
+
+    #[test]
+    fn test_error_invalid_progress() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let student = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+
+        env.mock_all_auths();
+        assert_eq!(
+            client.try_distribute_for_progress(&student, &101),
+            Err(Ok(Error::InvalidProgress))
+        );
+    }
+
+    #[test]
+    fn test_events() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let student1 = Address::generate(&env);
+        let student2 = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+
+        env.mock_all_auths();
+        client.mint(&student1, &100);
+        assert_eq!(
+            last_event(&env),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (symbol_short!("mint"), admin.clone(), student1.clone()).into_val(&env),
+                    100_i128.into_val(&env),
+                ),
+            ]
+        );
+
+        client.transfer(&student1, &student2, &30);
+        assert_eq!(
+            last_event(&env),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (
+                        symbol_short!("transfer"),
+                        student1.clone(),
+                        student2.clone()
+                    )
+                        .into_val(&env),
+                    30_i128.into_val(&env),
+                ),
+            ]
+        );
+
+        client.distribute_for_progress(&student2, &25);
+        assert_eq!(
+            last_event(&env),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (symbol_short!("distrib"), admin.clone(), student2.clone()).into_val(&env),
+                    (25_u32, 25_i128).into_val(&env),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sep41_token_client() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+        let token = token::Client::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+
+        env.mock_all_auths();
+        client.mint(&owner, &1000);
+
+        assert_eq!(token.name(), name);
+        assert_eq!(token.symbol(), symbol);
+        assert_eq!(token.decimals(), 7);
+        assert_eq!(token.balance(&owner), 1000);
+
+        token.transfer(&owner, &recipient, &100);
+        assert_eq!(token.balance(&owner), 900);
+        assert_eq!(token.balance(&recipient), 100);
+
+        token.approve(&owner, &spender, &300, &200);
+        assert_eq!(token.allowance(&owner, &spender), 300);
+        assert_eq!(
+            last_event(&env),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (symbol_short!("approve"), owner.clone(), spender.clone()).into_val(&env),
+                    (300_i128, 200_u32).into_val(&env),
+                ),
+            ]
+        );
+
+        token.transfer_from(&spender, &owner, &recipient, &200);
+        assert_eq!(token.allowance(&owner, &spender), 100);
+        assert_eq!(token.balance(&owner), 700);
+        assert_eq!(token.balance(&recipient), 300);
+
+        token.burn_from(&spender, &owner, &100);
+        assert_eq!(token.allowance(&owner, &spender), 0);
+        assert_eq!(token.balance(&owner), 600);
+
+        token.burn(&recipient, &50);
+        assert_eq!(
+            last_event(&env),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (symbol_short!("burn"), recipient.clone()).into_val(&env),
+                    50_i128.into_val(&env),
+                ),
+            ]
+        );
+        assert_eq!(token.balance(&recipient), 250);
+        assert_eq!(client.total_supply(), 850);
+    }
+
+    #[test]
+    fn test_allowance_expires() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+
+        env.mock_all_auths();
+        client.mint(&owner, &100);
+        client.approve(&owner, &spender, &100, &50);
+        assert_eq!(client.allowance(&owner, &spender), 100);
+
+        env.ledger().set_sequence_number(51);
+        assert_eq!(client.allowance(&owner, &spender), 0);
+        assert_eq!(
+            client.try_transfer_from(&spender, &owner, &spender, &1),
+            Err(Ok(Error::InsufficientAllowance.into()))
+        );
+    }
+
+    #[test]
+    fn test_error_insufficient_allowance() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+
+        env.mock_all_auths();
+        client.mint(&owner, &100);
+        client.approve(&owner, &spender, &10, &100);
+        assert_eq!(
+            client.try_burn_from(&spender, &owner, &11),
+            Err(Ok(Error::InsufficientAllowance.into()))
+        );
+    }
+
+    #[test]
+    fn test_error_invalid_expiration() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        client.initialize(&admin, &name, &symbol);
+
+        env.mock_all_auths();
+        env.ledger().set_sequence_number(100);
+        assert_eq!(
+            client.try_approve(&owner, &spender, &10, &99),
+            Err(Ok(Error::InvalidExpiration.into()))
+        );
+
+        // Clearing an allowance is allowed with any expiration
+        client.approve(&owner, &spender, &0, &0);
+        assert_eq!(client.allowance(&owner, &spender), 0);
+    }
+
+    #[test]
+    fn test_balances_survive_across_ledgers() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipToken);
+        let client = ScholarshipTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let student = Address::generate(&env);
+        let name = String::from_str(&env, "Scholarship Token");
+        let symbol = String::from_str(&env, "BRS");
+
+        env.mock_all_auths();
+        client.initialize(&admin, &name, &symbol);
+        client.mint(&admin, &1000);
+
+        // Monthly stipend for two years
+        for _ in 0..24 {
+            env.ledger()
+                .set_sequence_number(env.ledger().sequence() + 25 * DAY_IN_LEDGERS);
+            client.transfer(&admin, &student, &10);
+        }
+
+        assert_eq!(client.balance(&admin), 760);
+        assert_eq!(client.balance(&student), 240);
+        assert_eq!(client.total_supply(), 1000);
+        assert_eq!(client.name(), name);
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+            assert_eq!(
+                env.storage()
+                    .persistent()
+                    .get_ttl(&DataKey::Balance(student.clone())),
+                BALANCE_BUMP_AMOUNT
+            );
+        });
+    }
 }
@@ -1,5 +1,39 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, String, Symbol, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    ProgressOverflow = 1,
+    AlreadyInitialized = 2,
+    NotInitialized = 3,
+    NotAdmin = 4,
+    NotReporter = 5,
+    ReporterAlreadyExists = 6,
+    ReporterNotFound = 7,
+    CourseNotFound = 8,
+    CourseAlreadyExists = 9,
+    InvalidWeight = 10,
+    ProgressAboveMax = 11,
+    EntryNotFound = 12,
+    InvalidCorrection = 13,
+    Unauthorized = 14,
+    ProgressAlreadyPaid = 15,
+    EscrowAlreadyExists = 16,
+    EscrowNotFound = 17,
+    NotEscrow = 18,
+    TooManyCourses = 19,
+    NotInstitution = 20,
+    InstitutionAlreadyExists = 21,
+    InstitutionNotFound = 22,
+    ResultsRootNotFound = 23,
+    ResultsRootRevoked = 24,
+    InvalidEvidenceHash = 25,
+}
 
 // Student information
 #[contracttype]
@@ -10,13 +44,109 @@ pub struct StudentInfo {
     pub last_update: u64,
 }
 
-// Storage keys for persistent data
+// Course in the registry, `weight` is its share of the overall percentage
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Course {
+    pub id: u32,
+    pub title: String,
+    pub weight: u32,
+}
+
+// A student's progress in one course, 0-100
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CourseProgress {
+    pub course_id: u32,
+    pub progress: u32,
+}
+
+// Why an entry was adjusted or revoked
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ReasonCode {
+    DataEntryError = 1,
+    Duplicate = 2,
+    Regraded = 3,
+    AcademicMisconduct = 4,
+    Other = 5,
+}
+
+// One entry in a student's append-only progress log
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgressEntry {
+    pub index: u32,
+    pub course_id: u32,
+    pub progress: u32, // amount reported, or the corrected amount for `corrects`
+    pub reporter: Address, // reporter, or whoever made the correction
+    pub timestamp: u64,
+    pub evidence_hash: BytesN<32>,
+    pub corrects: Option<u32>,    // index of the entry this one corrects
+    pub reason_code: Option<u32>, // `ReasonCode` of a correction
+    pub reason: Option<String>,
+    pub flagged: bool, // correction reduced progress an escrow already paid out on
+}
+
+// Exam result in a batch; leaves of a results root are the sha256 of its XDR encoding
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExamResult {
+    pub student: Address,
+    pub course_id: u32,
+    pub score: u32,
+}
+
+// Merkle root of a batch of exam results posted by an institution
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResultsRoot {
+    pub id: u32,
+    pub root: BytesN<32>,
+    pub institution: Address,
+    pub posted_at: u64,
+    pub revoked: bool, // revoked roots no longer verify
+}
+
+const MAX_COURSE_PROGRESS: u32 = 100;
+const MAX_COURSES: u32 = 50; // bounds the per-course loops in the progress views
+const MAX_PROOF_DEPTH: u32 = 32;
+const MAX_PAGE_SIZE: u32 = 50;
+
+const DAY_IN_LEDGERS: u32 = 17_280;
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - 7 * DAY_IN_LEDGERS;
+
+// Storage keys
+// Admin and global totals live in instance storage; per-student and reporter
+// data is persistent and has its TTL extended whenever it is read or written.
 #[contracttype]
 pub enum DataKey {
-    TotalProgress,
-    LastStudent,
-    Student(Address),      // student address -> StudentInfo
-    AllStudents,           // Vec<Address> of all students
+    Admin,                        // instance
+    TotalProgress,                // instance
+    LastStudent,                  // instance
+    StudentCount,                 // instance, number of students in the index
+    CourseCount,                  // instance, number of registered courses
+    Course(u32),                  // course id -> Course
+    CourseAt(u32),                // index -> course id, in order of registration
+    Student(Address),             // student address -> StudentInfo
+    CourseProgress(Address, u32), // (student, course id) -> progress 0-100
+    StudentIndex(Address),        // student address -> position in the index
+    StudentAt(u32),               // index -> student address, in order of first progress
+    Reporter(Address),            // reporter address -> bool
+    CosignRequired(Address),      // student address -> bool
+    HistoryCount(Address),        // number of progress log entries for a student
+    History(Address, u32),        // (student, index) -> ProgressEntry
+    Correction(Address, u32),     // (student, index) -> index of the latest correction
+    Escrow(Address),              // escrow contract address -> bool
+    PaidCourse(Address, u32),     // (student, course id) -> progress an escrow paid out on
+    PaidTotal(Address),           // student -> total progress an escrow paid out on
+    ResultsCount,                 // instance, number of results roots posted
+    ResultsRoot(u32),             // batch id -> ResultsRoot
+    Institution(Address),         // institution address -> bool, may post results roots
 }
 
 #[contract]
@@ -24,147 +154,2135 @@ pub struct ScholarshipMilestone;
 
 #[contractimpl]
 impl ScholarshipMilestone {
-    /// Update student's milestone progress
-    /// Adds progress to total and updates last student
-    pub fn update_progress(env: Env, student: Address, progress: u32) {
-        // Get current total progress or default to 0
-        let current_total: u32 = env
+    /// Initialize the contract with the admin who manages reporters
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Self::extend_instance(&env);
+
+        Ok(())
+    }
+
+    /// Register a reporter (instructor, LMS bridge) allowed to submit progress (admin only)
+    /// Emits `("rep_add", reporter)`
+    pub fn add_reporter(env: Env, admin: Address, reporter: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Reporter(reporter.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::ReporterAlreadyExists);
+        }
+
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("rep_add"), reporter), ());
+
+        Ok(())
+    }
+
+    /// Remove a reporter (admin only)
+    /// Emits `("rep_rm", reporter)`
+    pub fn remove_reporter(env: Env, admin: Address, reporter: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Reporter(reporter.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::ReporterNotFound);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("rep_rm"), reporter), ());
+
+        Ok(())
+    }
+
+    /// Check whether an address is a registered reporter
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        Self::has_reporter(&env, &reporter)
+    }
+
+    /// Register an institution (exam board, registrar) allowed to post results roots (admin only)
+    /// Emits `("inst_add", institution)`
+    pub fn add_institution(env: Env, admin: Address, institution: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Institution(institution.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::InstitutionAlreadyExists);
+        }
+
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("inst_add"), institution), ());
+
+        Ok(())
+    }
+
+    /// Remove an institution (admin only); its roots stop verifying
+    /// Emits `("inst_rm", institution)`
+    pub fn remove_institution(env: Env, admin: Address, institution: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Institution(institution.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::InstitutionNotFound);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("inst_rm"), institution), ());
+
+        Ok(())
+    }
+
+    /// Check whether an address is a registered institution
+    pub fn is_institution(env: Env, institution: Address) -> bool {
+        Self::has_institution(&env, &institution)
+    }
+
+    /// Register a course with its weight in the overall percentage (admin only)
+    /// At most 50 courses can be registered
+    /// Emits `("course", course_id)` with `(title, weight)`
+    pub fn add_course(
+        env: Env,
+        admin: Address,
+        course_id: u32,
+        title: String,
+        weight: u32,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        if weight == 0 {
+            return Err(Error::InvalidWeight);
+        }
+
+        let key = DataKey::Course(course_id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::CourseAlreadyExists);
+        }
+        let count: u32 = env
             .storage()
-            .persistent()
-            .get(&DataKey::TotalProgress)
+            .instance()
+            .get(&DataKey::CourseCount)
             .unwrap_or(0);
+        if count >= MAX_COURSES {
+            return Err(Error::TooManyCourses);
+        }
 
-        // Add new progress to total
-        let new_total = current_total + progress;
+        let course = Course {
+            id: course_id,
+            title: title.clone(),
+            weight,
+        };
+        env.storage().persistent().set(&key, &course);
+        Self::extend_persistent(&env, &key);
 
-        // Save updated total progress
+        let index_key = DataKey::CourseAt(count);
+        env.storage().persistent().set(&index_key, &course_id);
+        Self::extend_persistent(&env, &index_key);
         env.storage()
-            .persistent()
-            .set(&DataKey::TotalProgress, &new_total);
+            .instance()
+            .set(&DataKey::CourseCount, &(count + 1));
 
-        // Save last student address
-        env.storage()
-            .persistent()
-            .set(&DataKey::LastStudent, &student);
+        env.events()
+            .publish((symbol_short!("course"), course_id), (title, weight));
 
-        // Update student info
-        let mut student_info: StudentInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Student(student.clone()))
-            .unwrap_or(StudentInfo {
-                address: student.clone(),
-                total_progress: 0,
-                last_update: 0,
-            });
+        Ok(())
+    }
 
-        student_info.total_progress += progress;
-        student_info.last_update = env.ledger().timestamp();
+    /// Get a course from the registry
+    pub fn get_course(env: Env, course_id: u32) -> Option<Course> {
+        let key = DataKey::Course(course_id);
+        let course = env.storage().persistent().get(&key);
+        if course.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        course
+    }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Student(student.clone()), &student_info);
+    /// Get every registered course
+    pub fn get_courses(env: Env) -> Vec<Course> {
+        let mut courses = Vec::new(&env);
+        for course_id in Self::course_ids(&env).iter() {
+            if let Some(course) = Self::get_course(env.clone(), course_id) {
+                courses.push_back(course);
+            }
+        }
+        courses
+    }
 
-        // Add student to all students list if not already there
-        let mut all_students: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::AllStudents)
-            .unwrap_or(Vec::new(&env));
-
-        let mut student_exists = false;
-        for i in 0..all_students.len() {
-            if all_students.get(i).unwrap() == student {
-                student_exists = true;
-                break;
+    /// Let a student require their own signature on every progress update for them
+    /// Emits `("cosign", student)` with `required`
+    pub fn set_cosign_required(env: Env, student: Address, required: bool) {
+        student.require_auth();
+
+        let key = DataKey::CosignRequired(student.clone());
+        if required {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events()
+            .publish((symbol_short!("cosign"), student), required);
+    }
+
+    /// Check whether a student co-signs their progress updates
+    pub fn is_cosign_required(env: Env, student: Address) -> bool {
+        let key = DataKey::CosignRequired(student);
+        let required = env.storage().persistent().has(&key);
+        if required {
+            Self::extend_persistent(&env, &key);
+        }
+        required
+    }
+
+    /// Update student's progress in a course (registered reporters only)
+    /// Students who opted in with `set_cosign_required` must sign as well
+    /// Course progress is capped at 100; the student and global totals add up all courses
+    /// Every update is appended to the student's progress log with its evidence hash,
+    /// which must not be all zeros
+    /// Emits `("progress", student)` with
+    /// `(reporter, course_id, progress, course_progress, student_total, total_progress)`
+    pub fn update_progress(
+        env: Env,
+        reporter: Address,
+        student: Address,
+        course_id: u32,
+        progress: u32,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        reporter.require_auth();
+        if !Self::has_reporter(&env, &reporter) {
+            return Err(Error::NotReporter);
+        }
+        if Self::is_cosign_required(env.clone(), student.clone()) {
+            student.require_auth();
+        }
+        if Self::get_course(env.clone(), course_id).is_none() {
+            return Err(Error::CourseNotFound);
+        }
+        Self::check_evidence(&evidence_hash)?;
+
+        // Cap the course at 100%
+        let course_progress = Self::get_course_progress(env.clone(), student.clone(), course_id)
+            .checked_add(progress)
+            .filter(|p| *p <= MAX_COURSE_PROGRESS)
+            .ok_or(Error::ProgressAboveMax)?;
+        let (student_total, new_total) =
+            Self::set_course_progress(&env, &student, course_id, course_progress)?;
+
+        Self::append_entry(
+            &env,
+            &student,
+            ProgressEntry {
+                index: 0,
+                course_id,
+                progress,
+                reporter: reporter.clone(),
+                timestamp: env.ledger().timestamp(),
+                evidence_hash,
+                corrects: None,
+                reason_code: None,
+                reason: None,
+                flagged: false,
+            },
+        );
+
+        Self::index_student(&env, &student);
+
+        env.events().publish(
+            (symbol_short!("progress"), student),
+            (
+                reporter,
+                course_id,
+                progress,
+                course_progress,
+                student_total,
+                new_total,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Adjust the amount of an earlier log entry by appending a correcting entry
+    /// Callable by the admin or the reporter of the original entry; history is never edited
+    /// The course, student and global totals move by the difference to the last amount
+    /// Progress an escrow already paid out on cannot be reduced by a reporter; the admin
+    /// can still do it and the correcting entry is flagged
+    /// Emits `("adjust", student)` with
+    /// `(caller, entry_index, reason_code, progress, course_progress, flagged)`
+    #[allow(clippy::too_many_arguments)]
+    pub fn adjust_progress(
+        env: Env,
+        caller: Address,
+        student: Address,
+        entry_index: u32,
+        progress: u32,
+        evidence_hash: BytesN<32>,
+        reason_code: ReasonCode,
+        reason: String,
+    ) -> Result<(), Error> {
+        Self::correct_entry(
+            &env,
+            symbol_short!("adjust"),
+            caller,
+            student,
+            entry_index,
+            progress,
+            Some(evidence_hash),
+            reason_code,
+            reason,
+        )
+    }
+
+    /// Revoke an earlier log entry, same as adjusting its amount to 0
+    /// Emits `("revoke", student)` with
+    /// `(caller, entry_index, reason_code, 0, course_progress, flagged)`
+    pub fn revoke_progress(
+        env: Env,
+        caller: Address,
+        student: Address,
+        entry_index: u32,
+        reason_code: ReasonCode,
+        reason: String,
+    ) -> Result<(), Error> {
+        Self::correct_entry(
+            &env,
+            symbol_short!("revoke"),
+            caller,
+            student,
+            entry_index,
+            0,
+            None,
+            reason_code,
+            reason,
+        )
+    }
+
+    /// Register an escrow contract that reports payouts (admin only)
+    /// Emits `("esc_add", escrow)`
+    pub fn add_escrow(env: Env, admin: Address, escrow: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Escrow(escrow.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::EscrowAlreadyExists);
+        }
+
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(&env, &key);
+
+        env.events().publish((symbol_short!("esc_add"), escrow), ());
+
+        Ok(())
+    }
+
+    /// Remove an escrow contract (admin only)
+    /// Emits `("esc_rm", escrow)`
+    pub fn remove_escrow(env: Env, admin: Address, escrow: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Escrow(escrow.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::EscrowNotFound);
+        }
+
+        env.storage().persistent().remove(&key);
+
+        env.events().publish((symbol_short!("esc_rm"), escrow), ());
+
+        Ok(())
+    }
+
+    /// Check whether an escrow contract is registered
+    pub fn is_escrow(env: Env, escrow: Address) -> bool {
+        let key = DataKey::Escrow(escrow);
+        let registered = env.storage().persistent().has(&key);
+        if registered {
+            Self::extend_persistent(&env, &key);
+        }
+        registered
+    }
+
+    /// Record that an escrow paid out on `progress` of a student's course, or of
+    /// their total when `course_id` is unset (registered escrows only)
+    /// Emits `("paid", student)` with `(escrow, course_id, progress)`
+    pub fn record_payout(
+        env: Env,
+        escrow: Address,
+        student: Address,
+        course_id: Option<u32>,
+        progress: u32,
+    ) -> Result<(), Error> {
+        escrow.require_auth();
+
+        let escrow_key = DataKey::Escrow(escrow.clone());
+        if !env.storage().persistent().has(&escrow_key) {
+            return Err(Error::NotEscrow);
+        }
+        Self::extend_persistent(&env, &escrow_key);
+
+        let key = match course_id {
+            Some(course_id) => DataKey::PaidCourse(student.clone(), course_id),
+            None => DataKey::PaidTotal(student.clone()),
+        };
+        let paid: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &paid.max(progress));
+        Self::extend_persistent(&env, &key);
+
+        env.events().publish(
+            (symbol_short!("paid"), student),
+            (escrow, course_id, progress),
+        );
+
+        Ok(())
+    }
+
+    /// Get the progress an escrow paid out on, for a course or the student total
+    pub fn get_paid_progress(env: Env, student: Address, course_id: Option<u32>) -> u32 {
+        let key = match course_id {
+            Some(course_id) => DataKey::PaidCourse(student, course_id),
+            None => DataKey::PaidTotal(student),
+        };
+        match env.storage().persistent().get(&key) {
+            Some(paid) => {
+                Self::extend_persistent(&env, &key);
+                paid
             }
+            None => 0,
         }
+    }
 
-        if !student_exists {
-            all_students.push_back(student);
-            env.storage()
-                .persistent()
-                .set(&DataKey::AllStudents, &all_students);
+    /// Post the Merkle root of a batch of exam results (registered institutions only)
+    /// Returns the batch id escrows check proofs against
+    /// Emits `("results", batch_id)` with `(institution, root)`
+    pub fn post_results_root(
+        env: Env,
+        institution: Address,
+        root: BytesN<32>,
+    ) -> Result<u32, Error> {
+        institution.require_auth();
+        if !Self::has_institution(&env, &institution) {
+            return Err(Error::NotInstitution);
+        }
+
+        let batch_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ResultsCount)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::ResultsCount, &batch_id);
+        Self::extend_instance(&env);
+
+        let key = DataKey::ResultsRoot(batch_id);
+        env.storage().persistent().set(
+            &key,
+            &ResultsRoot {
+                id: batch_id,
+                root: root.clone(),
+                institution: institution.clone(),
+                posted_at: env.ledger().timestamp(),
+                revoked: false,
+            },
+        );
+        Self::extend_persistent(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("results"), batch_id), (institution, root));
+
+        Ok(batch_id)
+    }
+
+    /// Revoke a results root, e.g. one posted by mistake (admin or the posting institution)
+    /// Results in it no longer verify; milestones already paid on them stay paid
+    /// Emits `("res_rev", batch_id)` with `caller`
+    pub fn revoke_results_root(env: Env, caller: Address, batch_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut batch =
+            Self::get_results_root(env.clone(), batch_id).ok_or(Error::ResultsRootNotFound)?;
+        if !Self::is_admin(&env, &caller) && batch.institution != caller {
+            return Err(Error::Unauthorized);
+        }
+        if batch.revoked {
+            return Err(Error::ResultsRootRevoked);
+        }
+
+        batch.revoked = true;
+        let key = DataKey::ResultsRoot(batch_id);
+        env.storage().persistent().set(&key, &batch);
+        Self::extend_persistent(&env, &key);
+
+        env.events()
+            .publish((symbol_short!("res_rev"), batch_id), caller);
+
+        Ok(())
+    }
+
+    /// Get a posted results root
+    pub fn get_results_root(env: Env, batch_id: u32) -> Option<ResultsRoot> {
+        let key = DataKey::ResultsRoot(batch_id);
+        let root = env.storage().persistent().get(&key);
+        if root.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        root
+    }
+
+    /// Check that `result` is a leaf of a batch posted by `institution`
+    /// `proof` lists the sibling hashes from the leaf up; each pair is hashed with
+    /// sha256 in sorted order, so no leaf position is needed
+    /// Unknown or revoked batches, batches of another or a removed institution and
+    /// proofs deeper than 32 levels do not verify
+    pub fn verify_result(
+        env: Env,
+        batch_id: u32,
+        institution: Address,
+        result: ExamResult,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let Some(batch) = Self::get_results_root(env.clone(), batch_id) else {
+            return false;
+        };
+        if batch.revoked
+            || batch.institution != institution
+            || !Self::has_institution(&env, &institution)
+            || proof.len() > MAX_PROOF_DEPTH
+        {
+            return false;
         }
+
+        let leaf = env.crypto().sha256(&result.to_xdr(&env)).to_bytes();
+        let root = proof.iter().fold(leaf, |node, sibling| {
+            let (low, high) = if node <= sibling {
+                (node, sibling)
+            } else {
+                (sibling, node)
+            };
+            let mut pair = Bytes::from(low);
+            pair.append(&Bytes::from(high));
+            env.crypto().sha256(&pair).to_bytes()
+        });
+        root == batch.root
     }
 
     /// Get total accumulated progress across all updates
     pub fn get_total_progress(env: Env) -> u32 {
         env.storage()
-            .persistent()
+            .instance()
             .get(&DataKey::TotalProgress)
             .unwrap_or(0)
     }
 
     /// Get the last student who submitted progress
     pub fn get_last_student(env: Env) -> Option<Address> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::LastStudent)
+        env.storage().instance().get(&DataKey::LastStudent)
     }
 
     /// Get student information
     pub fn get_student_info(env: Env, student: Address) -> Option<StudentInfo> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Student(student))
+        let key = DataKey::Student(student);
+        let info = env.storage().persistent().get(&key);
+        if info.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        info
+    }
+
+    /// Get a student's progress in one course, 0-100
+    pub fn get_course_progress(env: Env, student: Address, course_id: u32) -> u32 {
+        let key = DataKey::CourseProgress(student, course_id);
+        match env.storage().persistent().get(&key) {
+            Some(progress) => {
+                Self::extend_persistent(&env, &key);
+                progress
+            }
+            None => 0,
+        }
+    }
+
+    /// Get a student's progress in every registered course
+    pub fn get_student_courses(env: Env, student: Address) -> Vec<CourseProgress> {
+        let mut courses = Vec::new(&env);
+        for course_id in Self::course_ids(&env).iter() {
+            courses.push_back(CourseProgress {
+                course_id,
+                progress: Self::get_course_progress(env.clone(), student.clone(), course_id),
+            });
+        }
+        courses
+    }
+
+    /// Get a student's overall percentage, weighting each course by its `weight`
+    pub fn get_overall_progress(env: Env, student: Address) -> u32 {
+        let mut weighted: u64 = 0;
+        let mut total_weight: u64 = 0;
+        for course in Self::get_courses(env.clone()).iter() {
+            let progress = Self::get_course_progress(env.clone(), student.clone(), course.id);
+            weighted += progress as u64 * course.weight as u64;
+            total_weight += course.weight as u64;
+        }
+
+        if total_weight == 0 {
+            return 0;
+        }
+        (weighted / total_weight) as u32
+    }
+
+    /// Get one entry of a student's progress log
+    pub fn get_progress_entry(env: Env, student: Address, index: u32) -> Option<ProgressEntry> {
+        let key = DataKey::History(student, index);
+        let entry = env.storage().persistent().get(&key);
+        if entry.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        entry
+    }
+
+    /// Get one page of a student's progress log, oldest first
+    pub fn get_progress_history(
+        env: Env,
+        student: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ProgressEntry> {
+        let count = Self::get_progress_history_count(env.clone(), student.clone());
+        let start = offset.min(count);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut entries = Vec::new(&env);
+        for i in start..end {
+            if let Some(entry) = Self::get_progress_entry(env.clone(), student.clone(), i) {
+                entries.push_back(entry);
+            }
+        }
+        entries
+    }
+
+    /// Get number of entries in a student's progress log
+    pub fn get_progress_history_count(env: Env, student: Address) -> u32 {
+        let key = DataKey::HistoryCount(student);
+        let count = env.storage().persistent().get(&key);
+        if count.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        count.unwrap_or(0)
     }
 
     /// Get all students who have submitted progress
+    /// Reads every student; use `get_students` once there are many
     pub fn get_all_students(env: Env) -> Vec<StudentInfo> {
-        let addresses: Vec<Address> = env
+        let count = Self::get_student_count(env.clone());
+        Self::read_students(&env, 0, count)
+    }
+
+    /// Get one page of students, in the order they first submitted progress
+    pub fn get_students(env: Env, offset: u32, limit: u32) -> Vec<StudentInfo> {
+        let count = Self::get_student_count(env.clone());
+        let start = offset.min(count);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        Self::read_students(&env, start, end)
+    }
+
+    /// Get number of students who have submitted progress
+    pub fn get_student_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::StudentCount)
+            .unwrap_or(0)
+    }
+
+    /// Extend the storage TTL of a student's records, their course progress and
+    /// payout locks, one page of their progress log with its corrections, and the
+    /// contract instance
+    /// Anyone can call this to keep a long-running student from being archived; the
+    /// log is paged like `get_progress_history`
+    pub fn bump(env: Env, student: Address, offset: u32, limit: u32) {
+        Self::extend_instance(&env);
+        let extend = |key: DataKey| {
+            if env.storage().persistent().has(&key) {
+                Self::extend_persistent(&env, &key);
+            }
+        };
+
+        extend(DataKey::Student(student.clone()));
+        extend(DataKey::CosignRequired(student.clone()));
+        extend(DataKey::PaidTotal(student.clone()));
+        if let Some(index) = env
             .storage()
             .persistent()
-            .get(&DataKey::AllStudents)
-            .unwrap_or(Vec::new(&env));
-
-        let mut students = Vec::new(&env);
-        for i in 0..addresses.len() {
-            let addr = addresses.get(i).unwrap();
-            if let Some(info) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, StudentInfo>(&DataKey::Student(addr))
-            {
-                students.push_back(info);
-            }
+            .get::<_, u32>(&DataKey::StudentIndex(student.clone()))
+        {
+            extend(DataKey::StudentIndex(student.clone()));
+            extend(DataKey::StudentAt(index));
+        }
+        for course_id in Self::course_ids(&env).iter() {
+            extend(DataKey::Course(course_id));
+            extend(DataKey::CourseProgress(student.clone(), course_id));
+            extend(DataKey::PaidCourse(student.clone(), course_id));
+        }
+
+        let count = Self::get_progress_history_count(env.clone(), student.clone());
+        let start = offset.min(count);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        for i in start..end {
+            extend(DataKey::History(student.clone(), i));
+            extend(DataKey::Correction(student.clone(), i));
         }
-        students
+    }
+
+    /// Extend the storage TTL of a results root, its institution's registration and
+    /// the contract instance, so escrows can keep verifying results against it
+    pub fn bump_results_root(env: Env, batch_id: u32) -> Result<(), Error> {
+        let batch =
+            Self::get_results_root(env.clone(), batch_id).ok_or(Error::ResultsRootNotFound)?;
+        Self::has_institution(&env, &batch.institution);
+        Self::extend_instance(&env);
+        Ok(())
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
+impl ScholarshipMilestone {
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
 
-    #[test]
-    fn test_update_progress() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, ScholarshipMilestone);
-        let client = ScholarshipMilestoneClient::new(&env, &contract_id);
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored_admin != *admin {
+            return Err(Error::NotAdmin);
+        }
+        Self::extend_instance(env);
 
-        let student1 = Address::generate(&env);
-        let student2 = Address::generate(&env);
+        Ok(())
+    }
 
-        // First update
-        client.update_progress(&student1, &25);
-        assert_eq!(client.get_total_progress(), 25);
-        assert_eq!(client.get_last_student(), Some(student1.clone()));
+    /// Store a student's new progress in a course and move the student and
+    /// global totals by the difference. Returns `(student_total, total_progress)`
+    fn set_course_progress(
+        env: &Env,
+        student: &Address,
+        course_id: u32,
+        course_progress: u32,
+    ) -> Result<(u32, u32), Error> {
+        let course_key = DataKey::CourseProgress(student.clone(), course_id);
+        let previous: u32 = env.storage().persistent().get(&course_key).unwrap_or(0);
+        let apply = |total: u32| {
+            total
+                .checked_sub(previous)
+                .and_then(|t| t.checked_add(course_progress))
+                .ok_or(Error::ProgressOverflow)
+        };
 
-        // Second update
-        client.update_progress(&student2, &30);
-        assert_eq!(client.get_total_progress(), 55);
-        assert_eq!(client.get_last_student(), Some(student2));
+        let current_total: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalProgress)
+            .unwrap_or(0);
+        let new_total = apply(current_total)?;
+
+        let student_key = DataKey::Student(student.clone());
+        let mut student_info: StudentInfo =
+            env.storage()
+                .persistent()
+                .get(&student_key)
+                .unwrap_or(StudentInfo {
+                    address: student.clone(),
+                    total_progress: 0,
+                    last_update: 0,
+                });
+        student_info.total_progress = apply(student_info.total_progress)?;
+        student_info.last_update = env.ledger().timestamp();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalProgress, &new_total);
+        env.storage().instance().set(&DataKey::LastStudent, student);
+        Self::extend_instance(env);
+
+        env.storage().persistent().set(&student_key, &student_info);
+        Self::extend_persistent(env, &student_key);
+        env.storage()
+            .persistent()
+            .set(&course_key, &course_progress);
+        Self::extend_persistent(env, &course_key);
+
+        Ok((student_info.total_progress, new_total))
     }
 
-    #[test]
-    fn test_empty_state() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, ScholarshipMilestone);
-        let client = ScholarshipMilestoneClient::new(&env, &contract_id);
+    /// Shared by `adjust_progress` and `revoke_progress`, `evidence_hash` defaults to
+    /// the original entry's
+    #[allow(clippy::too_many_arguments)]
+    fn correct_entry(
+        env: &Env,
+        topic: Symbol,
+        caller: Address,
+        student: Address,
+        entry_index: u32,
+        progress: u32,
+        evidence_hash: Option<BytesN<32>>,
+        reason_code: ReasonCode,
+        reason: String,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if let Some(evidence_hash) = &evidence_hash {
+            Self::check_evidence(evidence_hash)?;
+        }
 
-        assert_eq!(client.get_total_progress(), 0);
-        assert_eq!(client.get_last_student(), None);
+        let original = Self::get_progress_entry(env.clone(), student.clone(), entry_index)
+            .ok_or(Error::EntryNotFound)?;
+        if original.corrects.is_some() {
+            return Err(Error::InvalidCorrection);
+        }
+        let is_admin = Self::is_admin(env, &caller);
+        if !is_admin && (original.reporter != caller || !Self::has_reporter(env, &caller)) {
+            return Err(Error::Unauthorized);
+        }
+
+        // Amount currently in effect for the original entry
+        let correction_key = DataKey::Correction(student.clone(), entry_index);
+        let current = match env.storage().persistent().get::<_, u32>(&correction_key) {
+            Some(latest) => {
+                Self::get_progress_entry(env.clone(), student.clone(), latest)
+                    .unwrap()
+                    .progress
+            }
+            None => original.progress,
+        };
+
+        let previous_course =
+            Self::get_course_progress(env.clone(), student.clone(), original.course_id);
+        let course_progress = previous_course
+            .saturating_sub(current)
+            .checked_add(progress)
+            .filter(|p| *p <= MAX_COURSE_PROGRESS)
+            .ok_or(Error::ProgressAboveMax)?;
+
+        // Reductions below what an escrow already paid out on
+        let student_total = Self::get_student_info(env.clone(), student.clone())
+            .map(|info| info.total_progress)
+            .unwrap_or(0)
+            .saturating_sub(previous_course)
+            + course_progress;
+        let flagged = course_progress
+            < Self::get_paid_progress(env.clone(), student.clone(), Some(original.course_id))
+            || student_total < Self::get_paid_progress(env.clone(), student.clone(), None);
+        if flagged && !is_admin {
+            return Err(Error::ProgressAlreadyPaid);
+        }
+
+        Self::set_course_progress(env, &student, original.course_id, course_progress)?;
+
+        let index = Self::append_entry(
+            env,
+            &student,
+            ProgressEntry {
+                index: 0,
+                course_id: original.course_id,
+                progress,
+                reporter: caller.clone(),
+                timestamp: env.ledger().timestamp(),
+                evidence_hash: evidence_hash.unwrap_or(original.evidence_hash),
+                corrects: Some(entry_index),
+                reason_code: Some(reason_code as u32),
+                reason: Some(reason),
+                flagged,
+            },
+        );
+        env.storage().persistent().set(&correction_key, &index);
+        Self::extend_persistent(env, &correction_key);
+
+        env.events().publish(
+            (topic, student),
+            (
+                caller,
+                entry_index,
+                reason_code,
+                progress,
+                course_progress,
+                flagged,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Append an entry to a student's progress log and return its index
+    fn append_entry(env: &Env, student: &Address, mut entry: ProgressEntry) -> u32 {
+        let count_key = DataKey::HistoryCount(student.clone());
+        let index: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let entry_key = DataKey::History(student.clone(), index);
+        entry.index = index;
+        env.storage().persistent().set(&entry_key, &entry);
+        env.storage().persistent().set(&count_key, &(index + 1));
+        Self::extend_persistent(env, &entry_key);
+        Self::extend_persistent(env, &count_key);
+        index
+    }
+
+    /// Add a student to the index on their first update, and keep their
+    /// index entries alive on every later one
+    fn index_student(env: &Env, student: &Address) {
+        let presence_key = DataKey::StudentIndex(student.clone());
+        let index = match env.storage().persistent().get::<_, u32>(&presence_key) {
+            Some(index) => index,
+            None => {
+                let index: u32 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::StudentCount)
+                    .unwrap_or(0);
+                env.storage().persistent().set(&presence_key, &index);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::StudentAt(index), student);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::StudentCount, &(index + 1));
+                index
+            }
+        };
+        Self::extend_persistent(env, &presence_key);
+        Self::extend_persistent(env, &DataKey::StudentAt(index));
+    }
+
+    /// Read student info for index entries `start..end`
+    fn read_students(env: &Env, start: u32, end: u32) -> Vec<StudentInfo> {
+        let mut students = Vec::new(env);
+        for i in start..end {
+            let key = DataKey::StudentAt(i);
+            let student: Address = env.storage().persistent().get(&key).unwrap();
+            Self::extend_persistent(env, &key);
+            if let Some(info) = Self::get_student_info(env.clone(), student) {
+                students.push_back(info);
+            }
+        }
+        students
+    }
+
+    fn course_ids(env: &Env) -> Vec<u32> {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CourseCount)
+            .unwrap_or(0);
+        let mut course_ids = Vec::new(env);
+        for i in 0..count {
+            let key = DataKey::CourseAt(i);
+            course_ids.push_back(env.storage().persistent().get(&key).unwrap());
+            Self::extend_persistent(env, &key);
+        }
+        course_ids
+    }
+
+    fn is_admin(env: &Env, address: &Address) -> bool {
+        env.storage().instance().get::<_, Address>(&DataKey::Admin) == Some(address.clone())
+    }
+
+    /// An all-zero hash points at no evidence
+    fn check_evidence(evidence_hash: &BytesN<32>) -> Result<(), Error> {
+        if evidence_hash.to_array() == [0; 32] {
+            return Err(Error::InvalidEvidenceHash);
+        }
+        Ok(())
+    }
+
+    fn has_reporter(env: &Env, reporter: &Address) -> bool {
+        let key = DataKey::Reporter(reporter.clone());
+        let registered = env.storage().persistent().has(&key);
+        if registered {
+            Self::extend_persistent(env, &key);
+        }
+        registered
+    }
+
+    fn has_institution(env: &Env, institution: &Address) -> bool {
+        let key = DataKey::Institution(institution.clone());
+        let registered = env.storage().persistent().has(&key);
+        if registered {
+            Self::extend_persistent(env, &key);
+        }
+        registered
+    }
+
+    fn extend_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        env.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+    use soroban_sdk::testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger,
+    };
+    use soroban_sdk::{vec, IntoVal, Symbol};
+
+    const COURSE: u32 = 1;
+
+    fn evidence(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[7; 32])
+    }
+
+    /// Register and initialize the contract with one reporter and one course
+    fn setup(env: &Env) -> (ScholarshipMilestoneClient<'_>, Address, Address) {
+        let contract_id = env.register_contract(None, ScholarshipMilestone);
+        let client = ScholarshipMilestoneClient::new(env, &contract_id);
+
+        let admin = Address::generate(env);
+        let reporter = Address::generate(env);
+
+        env.mock_all_auths();
+        client.initialize(&admin);
+        client.add_reporter(&admin, &reporter);
+        client.add_course(&admin, &COURSE, &String::from_str(env, "Algorithms"), &1);
+
+        (client, admin, reporter)
+    }
+
+    #[test]
+    fn test_update_progress() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student1 = Address::generate(&env);
+        let student2 = Address::generate(&env);
+
+        // First update
+        client.update_progress(&reporter, &student1, &COURSE, &25, &evidence(&env));
+        assert_eq!(client.get_total_progress(), 25);
+        assert_eq!(client.get_last_student(), Some(student1.clone()));
+
+        // Second update
+        client.update_progress(&reporter, &student2, &COURSE, &30, &evidence(&env));
+        assert_eq!(client.get_total_progress(), 55);
+        assert_eq!(client.get_last_student(), Some(student2));
+    }
+
+    #[test]
+    fn test_empty_state() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ScholarshipMilestone);
+        let client = ScholarshipMilestoneClient::new(&env, &contract_id);
+
+        assert_eq!(client.get_total_progress(), 0);
+        assert_eq!(client.get_last_student(), None);
+    }
+
+    #[test]
+    fn test_update_requires_reporter_auth() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+
+        client.update_progress(&reporter, &student, &COURSE, &25, &evidence(&env));
+        assert_eq!(
+            env.auths(),
+            std::vec![(
+                reporter.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        client.address.clone(),
+                        Symbol::new(&env, "update_progress"),
+                        (
+                            reporter.clone(),
+                            student.clone(),
+                            COURSE,
+                            25_u32,
+                            evidence(&env)
+                        )
+                            .into_val(&env),
+                    )),
+                    sub_invocations: std::vec![],
+                }
+            )]
+        );
+
+        // Once the student opts in, both have to sign
+        client.set_cosign_required(&student, &true);
+        assert!(client.is_cosign_required(&student));
+        client.update_progress(&reporter, &student, &COURSE, &10, &evidence(&env));
+        let signers: std::vec::Vec<Address> = env
+            .auths()
+            .into_iter()
+            .map(|(address, _)| address)
+            .collect();
+        assert_eq!(signers, std::vec![reporter.clone(), student.clone()]);
+
+        client.set_cosign_required(&student, &false);
+        assert!(!client.is_cosign_required(&student));
+        assert_eq!(
+            client.get_student_info(&student).unwrap().total_progress,
+            35
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_update_without_cosign_fails() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+        client.set_cosign_required(&student, &true);
+
+        // Only the reporter signs
+        env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+            address: &reporter,
+            invoke: &soroban_sdk::testutils::MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "update_progress",
+                args: (
+                    reporter.clone(),
+                    student.clone(),
+                    COURSE,
+                    25_u32,
+                    evidence(&env),
+                )
+                    .into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        client.update_progress(&reporter, &student, &COURSE, &25, &evidence(&env));
+    }
+
+    #[test]
+    fn test_reporter_registry() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+
+        let lms = Address::generate(&env);
+        assert!(client.is_reporter(&reporter));
+        assert!(!client.is_reporter(&lms));
+
+        client.add_reporter(&admin, &lms);
+        assert!(client.is_reporter(&lms));
+
+        client.remove_reporter(&admin, &reporter);
+        assert!(!client.is_reporter(&reporter));
+        assert_eq!(
+            client.try_update_progress(
+                &reporter,
+                &Address::generate(&env),
+                &COURSE,
+                &10,
+                &evidence(&env)
+            ),
+            Err(Ok(Error::NotReporter))
+        );
+    }
+
+    #[test]
+    fn test_error_progress_above_max() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+
+        client.update_progress(&reporter, &student, &COURSE, &90, &evidence(&env));
+        assert_eq!(
+            client.try_update_progress(&reporter, &student, &COURSE, &11, &evidence(&env)),
+            Err(Ok(Error::ProgressAboveMax))
+        );
+        assert_eq!(
+            client.try_update_progress(&reporter, &student, &COURSE, &u32::MAX, &evidence(&env)),
+            Err(Ok(Error::ProgressAboveMax))
+        );
+
+        client.update_progress(&reporter, &student, &COURSE, &10, &evidence(&env));
+        assert_eq!(client.get_course_progress(&student, &COURSE), 100);
+        assert_eq!(client.get_total_progress(), 100);
+    }
+
+    #[test]
+    fn test_error_invalid_evidence_hash() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+        let zero = BytesN::from_array(&env, &[0; 32]);
+
+        assert_eq!(
+            client.try_update_progress(&reporter, &student, &COURSE, &25, &zero),
+            Err(Ok(Error::InvalidEvidenceHash))
+        );
+        assert_eq!(client.get_progress_history_count(&student), 0);
+
+        client.update_progress(&reporter, &student, &COURSE, &25, &evidence(&env));
+        assert_eq!(
+            client.try_adjust_progress(
+                &reporter,
+                &student,
+                &0,
+                &20,
+                &zero,
+                &ReasonCode::Regraded,
+                &String::from_str(&env, "regraded"),
+            ),
+            Err(Ok(Error::InvalidEvidenceHash))
+        );
+    }
+
+    #[test]
+    fn test_course_progress() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+
+        // Algorithms counts once, the thesis three times
+        let thesis = 2;
+        client.add_course(&admin, &thesis, &String::from_str(&env, "Thesis"), &3);
+        assert_eq!(
+            client.get_course(&thesis),
+            Some(Course {
+                id: thesis,
+                title: String::from_str(&env, "Thesis"),
+                weight: 3,
+            })
+        );
+        assert_eq!(client.get_courses().len(), 2);
+
+        let student = Address::generate(&env);
+        assert_eq!(client.get_overall_progress(&student), 0);
+
+        client.update_progress(&reporter, &student, &COURSE, &100, &evidence(&env));
+        client.update_progress(&reporter, &student, &thesis, &20, &evidence(&env));
+
+        assert_eq!(client.get_course_progress(&student, &COURSE), 100);
+        assert_eq!(client.get_course_progress(&student, &thesis), 20);
+        assert_eq!(
+            client.get_student_courses(&student),
+            vec![
+                &env,
+                CourseProgress {
+                    course_id: COURSE,
+                    progress: 100,
+                },
+                CourseProgress {
+                    course_id: thesis,
+                    progress: 20,
+                },
+            ]
+        );
+        // (100 * 1 + 20 * 3) / 4
+        assert_eq!(client.get_overall_progress(&student), 40);
+        assert_eq!(
+            client.get_student_info(&student).unwrap().total_progress,
+            120
+        );
+    }
+
+    #[test]
+    fn test_progress_history() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+        let lms = Address::generate(&env);
+        assert_eq!(client.get_progress_history_count(&student), 0);
+
+        env.ledger().set_timestamp(1_000);
+        client.update_progress(&reporter, &student, &COURSE, &10, &evidence(&env));
+        env.ledger().set_timestamp(2_000);
+        for _ in 0..59 {
+            client.update_progress(&reporter, &student, &COURSE, &1, &evidence(&env));
+        }
+
+        assert_eq!(client.get_progress_history_count(&student), 60);
+        assert_eq!(
+            client.get_progress_entry(&student, &0),
+            Some(ProgressEntry {
+                index: 0,
+                course_id: COURSE,
+                progress: 10,
+                reporter: reporter.clone(),
+                timestamp: 1_000,
+                evidence_hash: evidence(&env),
+                corrects: None,
+                reason_code: None,
+                reason: None,
+                flagged: false,
+            })
+        );
+        assert_eq!(client.get_progress_entry(&lms, &0), None);
+
+        // Pages are capped at 50 entries
+        let first = client.get_progress_history(&student, &0, &100);
+        assert_eq!(first.len(), 50);
+        assert_eq!(first.get(1).unwrap().timestamp, 2_000);
+        let last = client.get_progress_history(&student, &50, &50);
+        assert_eq!(last.len(), 10);
+        assert_eq!(last.get(9).unwrap().index, 59);
+        assert_eq!(client.get_progress_history(&student, &60, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_adjust_progress() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+        client.update_progress(&reporter, &student, &COURSE, &40, &evidence(&env));
+        client.update_progress(&reporter, &student, &COURSE, &20, &evidence(&env));
+
+        // Entry 0 should have been 30
+        let reason = String::from_str(&env, "typo in gradebook export");
+        let regraded = BytesN::from_array(&env, &[9; 32]);
+        client.adjust_progress(
+            &reporter,
+            &student,
+            &0,
+            &30,
+            &regraded,
+            &ReasonCode::DataEntryError,
+            &reason,
+        );
+
+        assert_eq!(client.get_course_progress(&student, &COURSE), 50);
+        assert_eq!(
+            client.get_student_info(&student).unwrap().total_progress,
+            50
+        );
+        assert_eq!(client.get_total_progress(), 50);
+
+        // The original entry is untouched, the correction is appended
+        assert_eq!(client.get_progress_history_count(&student), 3);
+        assert_eq!(
+            client.get_progress_entry(&student, &0).unwrap().progress,
+            40
+        );
+        let correction = client.get_progress_entry(&student, &2).unwrap();
+        assert_eq!(correction.progress, 30);
+        assert_eq!(correction.corrects, Some(0));
+        assert_eq!(
+            correction.reason_code,
+            Some(ReasonCode::DataEntryError as u32)
+        );
+        assert_eq!(correction.reason, Some(reason.clone()));
+        assert_eq!(correction.evidence_hash, regraded);
+        assert!(!correction.flagged);
+
+        // A second correction applies against the last corrected amount
+        client.adjust_progress(
+            &reporter,
+            &student,
+            &0,
+            &35,
+            &regraded,
+            &ReasonCode::Regraded,
+            &reason,
+        );
+        assert_eq!(client.get_course_progress(&student, &COURSE), 55);
+        assert_eq!(client.get_total_progress(), 55);
+
+        assert_eq!(
+            client.try_adjust_progress(
+                &reporter,
+                &student,
+                &0,
+                &90,
+                &regraded,
+                &ReasonCode::Regraded,
+                &reason,
+            ),
+            Err(Ok(Error::ProgressAboveMax))
+        );
+    }
+
+    #[test]
+    fn test_revoke_progress() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+
+        let student1 = Address::generate(&env);
+        let student2 = Address::generate(&env);
+        client.update_progress(&reporter, &student1, &COURSE, &40, &evidence(&env));
+        client.update_progress(&reporter, &student2, &COURSE, &25, &evidence(&env));
+        client.update_progress(&reporter, &student1, &COURSE, &40, &evidence(&env));
+
+        // The admin can revoke any reporter's entry
+        let reason = String::from_str(&env, "submitted twice");
+        client.revoke_progress(&admin, &student1, &1, &ReasonCode::Duplicate, &reason);
+
+        assert_eq!(client.get_course_progress(&student1, &COURSE), 40);
+        assert_eq!(
+            client.get_student_info(&student1).unwrap().total_progress,
+            40
+        );
+        assert_eq!(client.get_total_progress(), 65);
+
+        let revocation = client.get_progress_entry(&student1, &2).unwrap();
+        assert_eq!(revocation.progress, 0);
+        assert_eq!(revocation.reporter, admin);
+        assert_eq!(revocation.corrects, Some(1));
+        assert_eq!(revocation.evidence_hash, evidence(&env));
+        assert_eq!(
+            vec![&env, env.events().all().last().unwrap()],
+            vec![
+                &env,
+                (
+                    client.address.clone(),
+                    (symbol_short!("revoke"), student1.clone()).into_val(&env),
+                    (
+                        admin.clone(),
+                        1_u32,
+                        ReasonCode::Duplicate,
+                        0_u32,
+                        40_u32,
+                        false
+                    )
+                        .into_val(&env),
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paid_progress_is_locked() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+
+        let escrow = Address::generate(&env);
+        client.add_escrow(&admin, &escrow);
+
+        let student = Address::generate(&env);
+        let reason = String::from_str(&env, "wrong student");
+        client.update_progress(&reporter, &student, &COURSE, &30, &evidence(&env));
+        client.update_progress(&reporter, &student, &COURSE, &30, &evidence(&env));
+
+        // An escrow released a milestone that needed 50% of the course
+        client.record_payout(&escrow, &student, &Some(COURSE), &50);
+        assert_eq!(client.get_paid_progress(&student, &Some(COURSE)), 50);
+        assert_eq!(client.get_paid_progress(&student, &None), 0);
+
+        // Reporters cannot undo it, but can still reduce what was not paid for
+        assert_eq!(
+            client.try_revoke_progress(&reporter, &student, &1, &ReasonCode::Other, &reason),
+            Err(Ok(Error::ProgressAlreadyPaid))
+        );
+        client.adjust_progress(
+            &reporter,
+            &student,
+            &1,
+            &20,
+            &evidence(&env),
+            &ReasonCode::Regraded,
+            &reason,
+        );
+        assert_eq!(client.get_course_progress(&student, &COURSE), 50);
+
+        // The admin can, and the correction is flagged for follow-up
+        client.revoke_progress(
+            &admin,
+            &student,
+            &1,
+            &ReasonCode::AcademicMisconduct,
+            &reason,
+        );
+        assert_eq!(client.get_course_progress(&student, &COURSE), 30);
+        assert!(client.get_progress_entry(&student, &3).unwrap().flagged);
+
+        client.remove_escrow(&admin, &escrow);
+        assert_eq!(
+            client.try_record_payout(&escrow, &student, &None, &10),
+            Err(Ok(Error::NotEscrow))
+        );
+    }
+
+    #[test]
+    fn test_error_entry_not_found() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+        assert_eq!(
+            client.try_revoke_progress(
+                &reporter,
+                &student,
+                &0,
+                &ReasonCode::Other,
+                &String::from_str(&env, "missing"),
+            ),
+            Err(Ok(Error::EntryNotFound))
+        );
+    }
+
+    #[test]
+    fn test_error_invalid_correction() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+        let reason = String::from_str(&env, "regraded");
+        client.update_progress(&reporter, &student, &COURSE, &40, &evidence(&env));
+        client.revoke_progress(&reporter, &student, &0, &ReasonCode::Regraded, &reason);
+
+        // Corrections are made against the original entry, not another correction
+        assert_eq!(
+            client.try_revoke_progress(&reporter, &student, &1, &ReasonCode::Regraded, &reason),
+            Err(Ok(Error::InvalidCorrection))
+        );
+    }
+
+    #[test]
+    fn test_error_unauthorized() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+
+        let student = Address::generate(&env);
+        let reason = String::from_str(&env, "not mine");
+        client.update_progress(&reporter, &student, &COURSE, &40, &evidence(&env));
+
+        // Another reporter, or the student, cannot touch the entry
+        let lms = Address::generate(&env);
+        client.add_reporter(&admin, &lms);
+        assert_eq!(
+            client.try_revoke_progress(&lms, &student, &0, &ReasonCode::Other, &reason),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            client.try_revoke_progress(&student, &student, &0, &ReasonCode::Other, &reason),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        // Nor can the original reporter once removed from the registry
+        client.remove_reporter(&admin, &reporter);
+        assert_eq!(
+            client.try_revoke_progress(&reporter, &student, &0, &ReasonCode::Other, &reason),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_error_escrow_registry() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+
+        let escrow = Address::generate(&env);
+        assert_eq!(
+            client.try_remove_escrow(&admin, &escrow),
+            Err(Ok(Error::EscrowNotFound))
+        );
+        assert!(!client.is_escrow(&escrow));
+        client.add_escrow(&admin, &escrow);
+        assert!(client.is_escrow(&escrow));
+        assert_eq!(
+            client.try_add_escrow(&admin, &escrow),
+            Err(Ok(Error::EscrowAlreadyExists))
+        );
+    }
+
+    fn result_leaf(env: &Env, student: &Address, score: u32) -> BytesN<32> {
+        let result = ExamResult {
+            student: student.clone(),
+            course_id: COURSE,
+            score,
+        };
+        env.crypto().sha256(&result.to_xdr(env)).to_bytes()
+    }
+
+    fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        let mut pair = Bytes::from(low.clone());
+        pair.append(&Bytes::from(high.clone()));
+        env.crypto().sha256(&pair).to_bytes()
+    }
+
+    #[test]
+    fn test_results_root() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        let institution = Address::generate(&env);
+        client.add_institution(&admin, &institution);
+        assert!(client.is_institution(&institution));
+
+        let students = [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        let leaves = [
+            result_leaf(&env, &students[0], 80),
+            result_leaf(&env, &students[1], 65),
+            result_leaf(&env, &students[2], 92),
+        ];
+        let left = hash_pair(&env, &leaves[0], &leaves[1]);
+        let root = hash_pair(&env, &left, &leaves[2]);
+
+        env.ledger().set_timestamp(500);
+        assert_eq!(client.post_results_root(&institution, &root), 1);
+        assert_eq!(
+            vec![&env, env.events().all().last().unwrap()],
+            vec![
+                &env,
+                (
+                    client.address.clone(),
+                    (symbol_short!("results"), 1_u32).into_val(&env),
+                    (institution.clone(), root.clone()).into_val(&env),
+                ),
+            ]
+        );
+        assert_eq!(
+            client.get_results_root(&1),
+            Some(ResultsRoot {
+                id: 1,
+                root,
+                institution: institution.clone(),
+                posted_at: 500,
+                revoked: false,
+            })
+        );
+
+        let result = |student: &Address, score: u32| ExamResult {
+            student: student.clone(),
+            course_id: COURSE,
+            score,
+        };
+        assert!(client.verify_result(
+            &1,
+            &institution,
+            &result(&students[0], 80),
+            &vec![&env, leaves[1].clone(), leaves[2].clone()]
+        ));
+        assert!(client.verify_result(
+            &1,
+            &institution,
+            &result(&students[2], 92),
+            &vec![&env, left.clone()]
+        ));
+
+        // A different score, a wrong proof, an unknown batch or another institution do not verify
+        assert!(!client.verify_result(
+            &1,
+            &institution,
+            &result(&students[2], 100),
+            &vec![&env, left.clone()]
+        ));
+        assert!(!client.verify_result(
+            &1,
+            &institution,
+            &result(&students[0], 80),
+            &vec![&env, leaves[2].clone()]
+        ));
+        assert!(!client.verify_result(
+            &2,
+            &institution,
+            &result(&students[2], 92),
+            &vec![&env, left.clone()]
+        ));
+        assert!(!client.verify_result(
+            &1,
+            &admin,
+            &result(&students[2], 92),
+            &vec![&env, left.clone()]
+        ));
+
+        // Nor does a batch of a removed institution
+        client.remove_institution(&admin, &institution);
+        assert!(!client.verify_result(
+            &1,
+            &institution,
+            &result(&students[2], 92),
+            &vec![&env, left]
+        ));
+    }
+
+    #[test]
+    fn test_revoke_results_root() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        let institution = Address::generate(&env);
+        client.add_institution(&admin, &institution);
+
+        let student = Address::generate(&env);
+        let leaf = result_leaf(&env, &student, 80);
+        let sibling = BytesN::from_array(&env, &[3; 32]);
+        let root = hash_pair(&env, &leaf, &sibling);
+        let result = ExamResult {
+            student,
+            course_id: COURSE,
+            score: 80,
+        };
+        let proof = vec![&env, sibling];
+
+        // The posting institution revokes its own root
+        let batch_id = client.post_results_root(&institution, &root);
+        assert!(client.verify_result(&batch_id, &institution, &result, &proof));
+        assert_eq!(
+            client.try_revoke_results_root(&Address::generate(&env), &batch_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        client.revoke_results_root(&institution, &batch_id);
+        assert_eq!(
+            vec![&env, env.events().all().last().unwrap()],
+            vec![
+                &env,
+                (
+                    client.address.clone(),
+                    (symbol_short!("res_rev"), batch_id).into_val(&env),
+                    institution.clone().into_val(&env),
+                ),
+            ]
+        );
+        assert!(client.get_results_root(&batch_id).unwrap().revoked);
+        assert!(!client.verify_result(&batch_id, &institution, &result, &proof));
+        assert_eq!(
+            client.try_revoke_results_root(&admin, &batch_id),
+            Err(Ok(Error::ResultsRootRevoked))
+        );
+
+        // So can the admin
+        let batch_id = client.post_results_root(&institution, &root);
+        client.revoke_results_root(&admin, &batch_id);
+        assert!(!client.verify_result(&batch_id, &institution, &result, &proof));
+        assert_eq!(
+            client.try_revoke_results_root(&admin, &(batch_id + 1)),
+            Err(Ok(Error::ResultsRootNotFound))
+        );
+    }
+
+    #[test]
+    fn test_error_institution_registry() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+
+        let institution = Address::generate(&env);
+        assert_eq!(
+            client.try_remove_institution(&admin, &institution),
+            Err(Ok(Error::InstitutionNotFound))
+        );
+        client.add_institution(&admin, &institution);
+        assert_eq!(
+            client.try_add_institution(&admin, &institution),
+            Err(Ok(Error::InstitutionAlreadyExists))
+        );
+
+        // Reporters submit progress but cannot post results roots
+        assert_eq!(
+            client.try_post_results_root(&reporter, &evidence(&env)),
+            Err(Ok(Error::NotInstitution))
+        );
+    }
+
+    #[test]
+    fn test_student_index() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+        env.budget().reset_unlimited();
+
+        let students: std::vec::Vec<Address> = (0..60).map(|_| Address::generate(&env)).collect();
+        for student in &students {
+            client.update_progress(&reporter, student, &COURSE, &5, &evidence(&env));
+        }
+        // Repeat reports do not add duplicates
+        client.update_progress(&reporter, &students[0], &COURSE, &5, &evidence(&env));
+
+        assert_eq!(client.get_student_count(), 60);
+        assert_eq!(client.get_all_students().len(), 60);
+
+        // Pages are capped at 50 students
+        let first = client.get_students(&0, &100);
+        assert_eq!(first.len(), 50);
+        assert_eq!(first.get(0).unwrap().address, students[0]);
+        assert_eq!(first.get(0).unwrap().total_progress, 10);
+        let last = client.get_students(&50, &50);
+        assert_eq!(last.len(), 10);
+        assert_eq!(last.get(9).unwrap().address, students[59]);
+        assert_eq!(client.get_students(&60, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_student_index_at_scale() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+        env.budget().reset_unlimited();
+
+        let add_students = |count: u32| {
+            for _ in 0..count {
+                client.update_progress(
+                    &reporter,
+                    &Address::generate(&env),
+                    &COURSE,
+                    &1,
+                    &evidence(&env),
+                );
+            }
+        };
+        let page_cost = |offset: u32| {
+            env.budget().reset_default();
+            assert_eq!(client.get_students(&offset, &10).len(), 10);
+            let cost = env.budget().cpu_instruction_cost();
+            env.budget().reset_unlimited();
+            cost
+        };
+
+        add_students(20);
+        let small = page_cost(0);
+
+        add_students(300);
+        let large = page_cost(0);
+        let deep = page_cost(300);
+        assert_eq!(client.get_student_count(), 320);
+
+        // Registering one more student still fits the default budget
+        env.budget().reset_default();
+        client.update_progress(
+            &reporter,
+            &Address::generate(&env),
+            &COURSE,
+            &1,
+            &evidence(&env),
+        );
+        env.budget().reset_unlimited();
+
+        // Page cost depends on the page, not on how many students exist. The
+        // test host's storage map lookups are logarithmic, hence the tolerance
+        // (scanning 16x the students would cost an order of magnitude more).
+        assert!(large <= small * 2, "{small} vs {large}");
+        assert!(deep <= large + large / 20, "{large} vs {deep}");
+    }
+
+    #[test]
+    fn test_error_course_not_found() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        assert_eq!(
+            client.try_update_progress(
+                &reporter,
+                &Address::generate(&env),
+                &42,
+                &10,
+                &evidence(&env)
+            ),
+            Err(Ok(Error::CourseNotFound))
+        );
+    }
+
+    #[test]
+    fn test_error_course_already_exists() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+
+        assert_eq!(
+            client.try_add_course(&admin, &COURSE, &String::from_str(&env, "Again"), &1),
+            Err(Ok(Error::CourseAlreadyExists))
+        );
+    }
+
+    #[test]
+    fn test_error_too_many_courses() {
+        let env = Env::default();
+        env.budget().reset_unlimited();
+        let (client, admin, _) = setup(&env);
+
+        for course_id in 2..=MAX_COURSES {
+            client.add_course(&admin, &course_id, &String::from_str(&env, "Course"), &1);
+        }
+        assert_eq!(client.get_courses().len(), MAX_COURSES);
+        assert_eq!(
+            client.try_add_course(
+                &admin,
+                &(MAX_COURSES + 1),
+                &String::from_str(&env, "One more"),
+                &1
+            ),
+            Err(Ok(Error::TooManyCourses))
+        );
+    }
+
+    #[test]
+    fn test_error_progress_overflow() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        // The global total is close to u32::MAX after years of updates
+        env.as_contract(&client.address, || {
+            env.storage()
+                .instance()
+                .set(&DataKey::TotalProgress, &(u32::MAX - 10));
+        });
+
+        let student = Address::generate(&env);
+        assert_eq!(
+            client.try_update_progress(&reporter, &student, &COURSE, &20, &evidence(&env)),
+            Err(Ok(Error::ProgressOverflow))
+        );
+        assert_eq!(client.get_total_progress(), u32::MAX - 10);
+        assert_eq!(client.get_course_progress(&student, &COURSE), 0);
+    }
+
+    #[test]
+    fn test_error_invalid_weight() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+
+        assert_eq!(
+            client.try_add_course(&admin, &2, &String::from_str(&env, "Audit"), &0),
+            Err(Ok(Error::InvalidWeight))
+        );
+    }
+
+    #[test]
+    fn test_error_already_initialized() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+
+        assert_eq!(
+            client.try_initialize(&admin),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_error_not_initialized() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ScholarshipMilestone);
+        let client = ScholarshipMilestoneClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        assert_eq!(
+            client.try_add_reporter(&admin, &Address::generate(&env)),
+            Err(Ok(Error::NotInitialized))
+        );
+    }
+
+    #[test]
+    fn test_error_not_admin() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+
+        assert_eq!(
+            client.try_add_reporter(&reporter, &Address::generate(&env)),
+            Err(Ok(Error::NotAdmin))
+        );
+        assert_eq!(
+            client.try_remove_reporter(&reporter, &reporter),
+            Err(Ok(Error::NotAdmin))
+        );
+    }
+
+    #[test]
+    fn test_error_not_reporter() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+
+        let student = Address::generate(&env);
+
+        // Students cannot report their own progress
+        assert_eq!(
+            client.try_update_progress(&student, &student, &COURSE, &100, &evidence(&env)),
+            Err(Ok(Error::NotReporter))
+        );
+        assert_eq!(client.get_total_progress(), 0);
+    }
+
+    #[test]
+    fn test_error_reporter_already_exists() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+
+        assert_eq!(
+            client.try_add_reporter(&admin, &reporter),
+            Err(Ok(Error::ReporterAlreadyExists))
+        );
+    }
+
+    #[test]
+    fn test_error_reporter_not_found() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+
+        assert_eq!(
+            client.try_remove_reporter(&admin, &Address::generate(&env)),
+            Err(Ok(Error::ReporterNotFound))
+        );
+    }
+
+    #[test]
+    fn test_progress_event() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+        let contract_id = client.address.clone();
+
+        let student1 = Address::generate(&env);
+        let student2 = Address::generate(&env);
+
+        client.update_progress(&reporter, &student1, &COURSE, &25, &evidence(&env));
+        client.update_progress(&reporter, &student2, &COURSE, &10, &evidence(&env));
+        client.update_progress(&reporter, &student1, &COURSE, &15, &evidence(&env));
+
+        assert_eq!(
+            env.events().all(),
+            vec![
+                &env,
+                (
+                    contract_id.clone(),
+                    (symbol_short!("rep_add"), reporter.clone()).into_val(&env),
+                    ().into_val(&env),
+                ),
+                (
+                    contract_id.clone(),
+                    (symbol_short!("course"), COURSE).into_val(&env),
+                    (String::from_str(&env, "Algorithms"), 1_u32).into_val(&env),
+                ),
+                (
+                    contract_id.clone(),
+                    (symbol_short!("progress"), student1.clone()).into_val(&env),
+                    (reporter.clone(), COURSE, 25_u32, 25_u32, 25_u32, 25_u32).into_val(&env),
+                ),
+                (
+                    contract_id.clone(),
+                    (symbol_short!("progress"), student2.clone()).into_val(&env),
+                    (reporter.clone(), COURSE, 10_u32, 10_u32, 10_u32, 35_u32).into_val(&env),
+                ),
+                (
+                    contract_id.clone(),
+                    (symbol_short!("progress"), student1.clone()).into_val(&env),
+                    (reporter.clone(), COURSE, 15_u32, 40_u32, 40_u32, 50_u32).into_val(&env),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_progress_survives_across_ledgers() {
+        let env = Env::default();
+        let (client, _, reporter) = setup(&env);
+        let contract_id = client.address.clone();
+
+        let student = Address::generate(&env);
+
+        // One report every 20 days for two years
+        client.update_progress(&reporter, &student, &COURSE, &1, &evidence(&env));
+        for _ in 0..36 {
+            env.ledger()
+                .set_sequence_number(env.ledger().sequence() + 20 * DAY_IN_LEDGERS);
+            client.update_progress(&reporter, &student, &COURSE, &1, &evidence(&env));
+        }
+
+        assert_eq!(client.get_total_progress(), 37);
+        assert_eq!(
+            client.get_student_info(&student).unwrap().total_progress,
+            37
+        );
+        assert_eq!(client.get_all_students().len(), 1);
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+            assert_eq!(
+                env.storage()
+                    .persistent()
+                    .get_ttl(&DataKey::Student(student.clone())),
+                PERSISTENT_BUMP_AMOUNT
+            );
+        });
+    }
+
+    #[test]
+    fn test_bump_keeps_student_alive() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+        let contract_id = client.address.clone();
+        let student = Address::generate(&env);
+
+        // A log with a correction, a payout lock and a results root
+        client.set_cosign_required(&student, &true);
+        client.update_progress(&reporter, &student, &COURSE, &40, &evidence(&env));
+        client.update_progress(&reporter, &student, &COURSE, &20, &evidence(&env));
+        client.adjust_progress(
+            &reporter,
+            &student,
+            &1,
+            &10,
+            &evidence(&env),
+            &ReasonCode::DataEntryError,
+            &String::from_str(&env, "typo"),
+        );
+        let escrow = Address::generate(&env);
+        client.add_escrow(&admin, &escrow);
+        client.record_payout(&escrow, &student, &Some(COURSE), &40);
+        let institution = Address::generate(&env);
+        client.add_institution(&admin, &institution);
+        let leaf = result_leaf(&env, &student, 90);
+        let batch_id = client.post_results_root(&institution, &leaf);
+
+        // Four years with a keeper bumping every 25 days and nothing else happening
+        let keeper = ScholarshipMilestoneClient::new(&env, &contract_id);
+        for _ in 0..60 {
+            env.ledger()
+                .set_sequence_number(env.ledger().sequence() + 25 * DAY_IN_LEDGERS);
+            keeper.bump(&student, &0, &50);
+            keeper.bump_results_root(&batch_id);
+        }
+
+        env.as_contract(&contract_id, || {
+            for key in [
+                DataKey::Student(student.clone()),
+                DataKey::StudentIndex(student.clone()),
+                DataKey::StudentAt(0),
+                DataKey::CosignRequired(student.clone()),
+                DataKey::CourseProgress(student.clone(), COURSE),
+                DataKey::PaidCourse(student.clone(), COURSE),
+                DataKey::HistoryCount(student.clone()),
+                DataKey::History(student.clone(), 2),
+                DataKey::Correction(student.clone(), 1),
+                DataKey::ResultsRoot(batch_id),
+                DataKey::Institution(institution.clone()),
+            ] {
+                assert_eq!(
+                    env.storage().persistent().get_ttl(&key),
+                    PERSISTENT_BUMP_AMOUNT
+                );
+            }
+        });
+        assert_eq!(client.get_progress_history(&student, &0, &50).len(), 3);
+        assert_eq!(client.get_paid_progress(&student, &Some(COURSE)), 40);
+        assert!(client.verify_result(
+            &batch_id,
+            &institution,
+            &ExamResult {
+                student: student.clone(),
+                course_id: COURSE,
+                score: 90,
+            },
+            &Vec::new(&env),
+        ));
+        assert_eq!(
+            client.try_bump_results_root(&(batch_id + 1)),
+            Err(Ok(Error::ResultsRootNotFound))
+        );
     }
 }
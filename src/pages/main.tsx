@@ -3,15 +3,18 @@ import { useRouter } from "next/router";
 import { getStoredWallet, disconnectWallet } from "@/utils/wallet";
 import {
   updateProgress,
+  hashEvidence,
   getTotalProgress,
   getLastStudent,
 } from "@/utils/contract";
+import { validateProof } from "@/utils/validation";
 
 export default function Main() {
   const router = useRouter();
   const [publicKey, setPublicKey] = useState<string | null>(null);
   const [studentAddress, setStudentAddress] = useState("");
   const [progress, setProgress] = useState("");
+  const [evidence, setEvidence] = useState("");
   const [isSubmitting, setIsSubmitting] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [success, setSuccess] = useState<string | null>(null);
@@ -71,6 +74,11 @@ export default function Main() {
       return;
     }
 
+    if (!validateProof(evidence.trim())) {
+      setError("Evidence must be a URL or a SHA-256 hash");
+      return;
+    }
+
     if (!publicKey) {
       setError("Wallet not connected");
       return;
@@ -83,7 +91,8 @@ export default function Main() {
       const txHash = await updateProgress(
         studentAddress,
         progressValue,
-        publicKey
+        publicKey,
+        await hashEvidence(evidence)
       );
 
       console.log("Transaction hash:", txHash);
@@ -97,6 +106,7 @@ export default function Main() {
       // Clear form
       setStudentAddress("");
       setProgress("");
+      setEvidence("");
     } catch (err: any) {
       console.error("Failed to update progress:", err);
       setError(err?.message || "Failed to update progress. Please try again.");
@@ -212,6 +222,24 @@ export default function Main() {
               />
             </div>
 
+            <div>
+              <label
+                htmlFor="evidence"
+                className="block text-sm font-medium text-gray-700 mb-2"
+              >
+                Evidence (URL or SHA-256 hash)
+              </label>
+              <input
+                type="text"
+                id="evidence"
+                value={evidence}
+                onChange={(e) => setEvidence(e.target.value)}
+                placeholder="https://lms.example.edu/grades/123"
+                className="w-full px-4 py-2 border border-gray-300 rounded-lg focus:outline-none focus:ring-2 focus:ring-indigo-500"
+                disabled={isSubmitting}
+              />
+            </div>
+
             <button
               type="submit"
               disabled={isSubmitting}
@@ -9,6 +9,7 @@ import {
   scValToNative,
 } from "@stellar/stellar-sdk";
 import freighterApi from "@stellar/freighter-api";
+import { validateEvidenceHash, validateHash } from "./validation";
 
 // Testnet configuration
 const RPC_URL = "https://soroban-testnet.stellar.org";
@@ -20,14 +21,44 @@ export const CONTRACT_ID =
 
 const server = new SorobanRpc.Server(RPC_URL);
 
+// Course progress is reported against when the caller does not pick one
+export const DEFAULT_COURSE_ID = 1;
+
+/**
+ * Turn evidence into the 32-byte hash recorded with a progress update
+ * A hex SHA-256 hash is used as is, anything else (e.g. a URL) is hashed with SHA-256
+ */
+export const hashEvidence = async (evidence: string): Promise<Uint8Array> => {
+  const trimmed = evidence.trim();
+  if (validateHash(trimmed)) {
+    return Uint8Array.from(
+      trimmed.match(/../g)!.map((byte) => parseInt(byte, 16))
+    );
+  }
+  const digest = await crypto.subtle.digest(
+    "SHA-256",
+    new TextEncoder().encode(trimmed)
+  );
+  return new Uint8Array(digest);
+};
+
 /**
  * Update student milestone progress
+ * The connected wallet signs as the reporter and must be registered on the contract
+ * `evidenceHash` is the 32-byte hash of the evidence kept in the student's progress log;
+ * an all-zero hash is rejected so every entry points at real evidence
  */
 export const updateProgress = async (
   studentAddress: string,
   progress: number,
-  userPublicKey: string
+  userPublicKey: string,
+  evidenceHash: Uint8Array,
+  courseId: number = DEFAULT_COURSE_ID
 ): Promise<string> => {
+  if (!validateEvidenceHash(evidenceHash)) {
+    throw new Error("Evidence hash must be 32 bytes and not all zeros");
+  }
+
   try {
     // Load account
     const sourceAccount = await server.getAccount(userPublicKey);
@@ -37,8 +68,11 @@ export const updateProgress = async (
 
     const operation = contract.call(
       "update_progress",
+      nativeToScVal(Address.fromString(userPublicKey), { type: "address" }),
       nativeToScVal(Address.fromString(studentAddress), { type: "address" }),
-      nativeToScVal(progress, { type: "u32" })
+      nativeToScVal(courseId, { type: "u32" }),
+      nativeToScVal(progress, { type: "u32" }),
+      nativeToScVal(evidenceHash, { type: "bytes" })
     );
 
     // Build transaction
@@ -84,6 +84,16 @@ export const validateHash = (hash: string): boolean => {
   return /^[a-fA-F0-9]{64}$/.test(hash);
 };
 
+/**
+ * Validates a raw evidence hash before it is recorded on-chain
+ * @param hash - The hash bytes to validate
+ * @returns true if 32 bytes and not all zeros
+ */
+export const validateEvidenceHash = (hash: Uint8Array): boolean => {
+  if (!(hash instanceof Uint8Array) || hash.length !== 32) return false;
+  return hash.some((byte) => byte !== 0);
+};
+
 /**
  * Validates proof data (URL or hash)
  * @param proof - The proof data to validate